@@ -0,0 +1,198 @@
+// Counts the bytes the process actually allocates, so the budget's modeled
+// `MEM` charge can be checked against real allocations.
+//
+// The allocator is always installed but only counts after `enable`, so runs
+// without `--track-alloc` pay a single relaxed load per allocation.
+
+use crate::measurement::Measurement;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Per thread, so each worker only sees its own host's allocations. Memory
+// freed on another thread than it was allocated on makes `LIVE` drift, which
+// only matters across calls, not within one.
+thread_local! {
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn record(grown: i64) {
+    // `try_with` because allocations also happen while thread-locals are
+    // being torn down.
+    let _ = LIVE.try_with(|live| {
+        let now = live.get() + grown;
+        live.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+    });
+    if grown > 0 {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + grown as u64));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(layout.size() as i64);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(layout.size() as i64);
+        }
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(-(layout.size() as i64));
+        }
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(new_size as i64 - layout.size() as i64);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AllocStats {
+    // Bytes allocated, counting only the growth of reallocations.
+    pub bytes: u64,
+    // Largest amount of memory held at once above what was live at the start.
+    pub peak: u64,
+    // Growth of the process' peak RSS in kB. Process-wide, so only meaningful
+    // with a single worker.
+    pub rss_peak: Option<u64>,
+}
+
+pub struct AllocSnapshot {
+    allocated: u64,
+    live: i64,
+    rss: Option<u64>,
+}
+
+impl AllocSnapshot {
+    // Reading `/proc` allocates too, so it happens outside the counted span.
+    pub fn take() -> AllocSnapshot {
+        let rss = rss::reset_peak();
+        let live = LIVE.with(Cell::get);
+        PEAK.with(|peak| peak.set(live));
+        AllocSnapshot {
+            allocated: ALLOCATED.with(Cell::get),
+            live,
+            rss,
+        }
+    }
+
+    pub fn since(&self) -> AllocStats {
+        let bytes = ALLOCATED.with(Cell::get) - self.allocated;
+        let peak = (PEAK.with(Cell::get) - self.live).max(0) as u64;
+        AllocStats {
+            bytes,
+            peak,
+            rss_peak: self
+                .rss
+                .and_then(|before| rss::peak().map(|peak| peak.saturating_sub(before))),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod rss {
+    use std::fs;
+
+    fn status_kb(field: &str) -> Option<u64> {
+        let status = fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|line| line.starts_with(field))?;
+        line[field.len()..]
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    // Resets the peak RSS to the current RSS and returns the latter, or
+    // `None` if the kernel doesn't allow resetting it.
+    pub fn reset_peak() -> Option<u64> {
+        fs::write("/proc/self/clear_refs", "5").ok()?;
+        status_kb("VmRSS:")
+    }
+
+    pub fn peak() -> Option<u64> {
+        status_kb("VmHWM:")
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod rss {
+    pub fn reset_peak() -> Option<u64> {
+        None
+    }
+
+    pub fn peak() -> Option<u64> {
+        None
+    }
+}
+
+// Collects allocated bytes against the `MEM` charge per syscall, to list
+// those whose real allocations greatly exceed the model at the end of a run.
+#[derive(Default)]
+pub struct AllocReport {
+    by_syscall: BTreeMap<Cow<'static, str>, Vec<(u64, u64)>>,
+}
+
+impl AllocReport {
+    // Rows without a `MEM` charge have nothing to compare against.
+    pub fn record(&mut self, measurement: &Measurement) {
+        if measurement.memory_bytes_cost == 0 {
+            return;
+        }
+        if let Some(allocations) = &measurement.allocations {
+            self.by_syscall
+                .entry(measurement.syscall_name.clone())
+                .or_default()
+                .push((allocations.bytes, measurement.memory_bytes_cost));
+        }
+    }
+
+    pub fn print(&self, ratio: f64) {
+        println!("syscalls allocating more than {}x their MEM charge:", ratio);
+        for (name, samples) in &self.by_syscall {
+            let exceeding = samples
+                .iter()
+                .filter(|(bytes, mem)| *bytes as f64 > *mem as f64 * ratio)
+                .count();
+            if exceeding == 0 {
+                continue;
+            }
+            let bytes: u64 = samples.iter().map(|(bytes, _)| bytes).sum();
+            let mem: u64 = samples.iter().map(|(_, mem)| mem).sum();
+            println!(
+                "  {} ({} of {} inputs): {} bytes allocated vs {} MEM on average",
+                name,
+                exceeding,
+                samples.len(),
+                bytes / samples.len() as u64,
+                mem / samples.len() as u64
+            );
+        }
+    }
+}
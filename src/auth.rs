@@ -0,0 +1,211 @@
+// Mocked and signed authorization for `RequireAuth` and `RequireAuthForArgs`.
+
+use crate::fuzzcontract::{FuzzInstruction, TypedFuzzInstruction, TypedModAddress};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use soroban_sdk::testutils::LedgerSnapshot;
+use soroban_sdk::xdr::{
+    AccountEntry, AccountEntryExt, AccountId, Hash, HashIdPreimage,
+    HashIdPreimageSorobanAuthorization, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey,
+    LedgerKeyAccount, ScAddress, ScBytes, ScMap, ScMapEntry, ScSymbol, ScVal, ScVec,
+    SequenceNumber, SorobanAddressCredentials, SorobanAuthorizationEntry,
+    SorobanAuthorizedContractFunction, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials, Thresholds, Uint256, WriteXdr,
+};
+use soroban_sdk::{Address, Env, FromVal, IntoVal, TryFromVal, Val, Vec};
+use std::cell::Cell;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthMode {
+    // Random addresses, no authorization.
+    None,
+    // `Env::mock_all_auths`: measures tree recording without signatures.
+    MockAll,
+    // Real ed25519-signed entries for generated accounts.
+    Signed,
+}
+
+impl FromStr for AuthMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<AuthMode, String> {
+        match s {
+            "none" => Ok(AuthMode::None),
+            "mock-all" => Ok(AuthMode::MockAll),
+            "signed" => Ok(AuthMode::Signed),
+            _ => Err(format!("unknown auth mode: {}", s)),
+        }
+    }
+}
+
+pub struct Account {
+    keypair: Keypair,
+    account_id: AccountId,
+}
+
+impl Account {
+    pub fn generate(rng: &mut impl Rng) -> Account {
+        let secret = SecretKey::from_bytes(&rng.gen::<[u8; 32]>()).unwrap();
+        let public = PublicKey::from(&secret);
+        let account_id = AccountId(soroban_sdk::xdr::PublicKey::PublicKeyTypeEd25519(Uint256(
+            public.to_bytes(),
+        )));
+        Account {
+            keypair: Keypair { secret, public },
+            account_id,
+        }
+    }
+
+    fn ledger_entry(&self) -> (Box<LedgerKey>, Box<LedgerEntry>) {
+        let key = LedgerKey::Account(LedgerKeyAccount {
+            account_id: self.account_id.clone(),
+        });
+        let entry = LedgerEntry {
+            last_modified_ledger_seq: 0,
+            data: LedgerEntryData::Account(AccountEntry {
+                account_id: self.account_id.clone(),
+                balance: 0,
+                seq_num: SequenceNumber(0),
+                num_sub_entries: 0,
+                inflation_dest: None,
+                flags: 0,
+                home_domain: Default::default(),
+                // Master key weight 1, all thresholds 0: one signature suffices.
+                thresholds: Thresholds([1, 0, 0, 0]),
+                signers: Default::default(),
+                ext: AccountEntryExt::V0,
+            }),
+            ext: LedgerEntryExt::V0,
+        };
+        (Box::new(key), Box::new(entry))
+    }
+
+    fn address(&self, env: &Env) -> Address {
+        let address = ScVal::Address(ScAddress::Account(self.account_id.clone()));
+        Address::from_val(env, &Val::try_from_val(env, &address).unwrap())
+    }
+}
+
+pub struct SignedAuth {
+    accounts: std::vec::Vec<Account>,
+    contract_id: Address,
+    next_nonce: Cell<i64>,
+}
+
+impl SignedAuth {
+    // The accounts have to exist in the ledger before the `Env` is built, so
+    // this returns the snapshot to create it from. It starts from a default
+    // `Env`'s, so the ledger info matches the other auth modes.
+    pub fn snapshot(accounts: &[Account]) -> LedgerSnapshot {
+        let mut snapshot = Env::default().to_snapshot();
+        snapshot
+            .ledger_entries
+            .extend(accounts.iter().map(Account::ledger_entry));
+        snapshot
+    }
+
+    pub fn new(accounts: std::vec::Vec<Account>, contract_id: &Address) -> SignedAuth {
+        SignedAuth {
+            accounts,
+            contract_id: contract_id.clone(),
+            next_nonce: Cell::new(0),
+        }
+    }
+
+    // Points the address of an auth instruction at one of the generated
+    // accounts and installs a signed entry authorizing exactly that call.
+    pub fn prepare(
+        &self,
+        env: &Env,
+        rng: &mut impl Rng,
+        instruction: TypedFuzzInstruction,
+    ) -> TypedFuzzInstruction {
+        let account = &self.accounts[rng.gen_range(0..self.accounts.len())];
+        let address = account.address(env);
+
+        let (instruction, args) = match instruction {
+            TypedFuzzInstruction::Address(TypedModAddress::RequireAuth(_)) => {
+                let instruction =
+                    TypedFuzzInstruction::Address(TypedModAddress::RequireAuth(address));
+                let call_arg: Val = FuzzInstruction::Typed(instruction.clone()).into_val(env);
+                (instruction, Vec::from_array(env, [call_arg]))
+            }
+            TypedFuzzInstruction::Address(TypedModAddress::RequireAuthForArgs(_, args)) => {
+                let instruction = TypedFuzzInstruction::Address(
+                    TypedModAddress::RequireAuthForArgs(address, args.clone()),
+                );
+                (instruction, args)
+            }
+            instruction => return instruction,
+        };
+
+        let entry = self.sign(env, account, &args);
+        env.set_auths(&[entry]);
+
+        instruction
+    }
+
+    fn sign(&self, env: &Env, account: &Account, args: &Vec<Val>) -> SorobanAuthorizationEntry {
+        let nonce = self.next_nonce.get();
+        self.next_nonce.set(nonce + 1);
+
+        let signature_expiration_ledger = env.ledger().sequence() + 100;
+
+        let contract_address = match to_sc_val(env, self.contract_id.to_val()) {
+            ScVal::Address(address) => address,
+            _ => unreachable!(),
+        };
+        let args: std::vec::Vec<ScVal> = args.iter().map(|arg| to_sc_val(env, arg)).collect();
+
+        let invocation = SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::ContractFn(SorobanAuthorizedContractFunction {
+                contract_address,
+                function_name: ScSymbol("run".try_into().unwrap()),
+                args: ScVec(args.try_into().unwrap()),
+            }),
+            sub_invocations: Default::default(),
+        };
+
+        let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+            network_id: Hash(env.ledger().network_id().to_array()),
+            nonce,
+            signature_expiration_ledger,
+            invocation: invocation.clone(),
+        });
+        let payload = Sha256::digest(preimage.to_xdr().unwrap());
+        let signature = account.keypair.sign(&payload);
+
+        let signature_args = ScVal::Map(Some(ScMap(
+            vec![
+                ScMapEntry {
+                    key: ScVal::Symbol(ScSymbol("public_key".try_into().unwrap())),
+                    val: ScVal::Bytes(ScBytes(
+                        account.keypair.public.to_bytes().try_into().unwrap(),
+                    )),
+                },
+                ScMapEntry {
+                    key: ScVal::Symbol(ScSymbol("signature".try_into().unwrap())),
+                    val: ScVal::Bytes(ScBytes(signature.to_bytes().try_into().unwrap())),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        )));
+
+        SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address: ScAddress::Account(account.account_id.clone()),
+                nonce,
+                signature_expiration_ledger,
+                signature_args: ScVec(vec![signature_args].try_into().unwrap()),
+            }),
+            root_invocation: invocation,
+        }
+    }
+}
+
+fn to_sc_val(env: &Env, val: Val) -> ScVal {
+    ScVal::try_from_val(env, &val).unwrap()
+}
@@ -0,0 +1,210 @@
+// `check`: runs a fixed-seed corpus and compares per-syscall medians against
+// the baseline checked into the repo, exiting non-zero on any syscall whose
+// charges or CPU/ns moved beyond tolerance. Wall time is noisier than the
+// charges, so CPU/ns has its own `--ns-tolerance`. `check --update` rewrites
+// the baseline after an intended change.
+
+use crate::config::Config;
+use crate::measurement::Measurement;
+use crate::results;
+use std::collections::BTreeMap;
+use std::fs;
+
+// Changing this changes the corpus, which invalidates the baseline.
+const CORPUS_SEED: u64 = 0;
+
+#[derive(Clone, Copy, Debug)]
+struct Medians {
+    cpu_instruction_cost: u64,
+    memory_bytes_cost: u64,
+    // Charged CPU instructions per nanosecond of wall time.
+    cpu_per_ns: f64,
+}
+
+impl Medians {
+    const HEADER: &'static str = "Syscalls|CPU|MEM|CPUPerNs";
+
+    fn of(measurements: &[Measurement]) -> Medians {
+        Medians {
+            cpu_instruction_cost: results::median(
+                measurements.iter().map(|m| m.cpu_instruction_cost),
+            ),
+            memory_bytes_cost: results::median(measurements.iter().map(|m| m.memory_bytes_cost)),
+            cpu_per_ns: results::median(
+                measurements
+                    .iter()
+                    .map(|m| m.cpu_instruction_cost as f64 / m.duration.as_nanos().max(1) as f64),
+            ),
+        }
+    }
+}
+
+struct CheckConfig {
+    baseline: String,
+    iterations: u64,
+    // Allowed relative change in the CPU and MEM medians.
+    tolerance: f64,
+    // Allowed relative change in the CPU/ns median.
+    ns_tolerance: f64,
+    update: bool,
+}
+
+impl CheckConfig {
+    fn from_args(mut args: impl Iterator<Item = String>) -> CheckConfig {
+        let mut config = CheckConfig {
+            baseline: "baseline.csv".to_string(),
+            iterations: 2000,
+            tolerance: 0.01,
+            ns_tolerance: 0.3,
+            update: false,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("missing value for {}", arg))
+            };
+            match arg.as_str() {
+                "--baseline" => config.baseline = value(),
+                "--iterations" => config.iterations = value().parse().unwrap(),
+                "--tolerance" => config.tolerance = value().parse().unwrap(),
+                "--ns-tolerance" => config.ns_tolerance = value().parse().unwrap(),
+                "--update" => config.update = true,
+                _ => panic!("unknown argument: {}", arg),
+            }
+        }
+
+        config
+    }
+}
+
+pub fn run(args: impl Iterator<Item = String>) {
+    let check = CheckConfig::from_args(args);
+
+    let config = Config {
+        iterations: check.iterations,
+        seed: CORPUS_SEED,
+        ..Config::default()
+    };
+
+    let mut measurements: Vec<Measurement> = Vec::new();
+    let warnings = crate::run(&config, |measurement| measurements.push(measurement));
+    for warning in warnings {
+        println!("warning: {}", warning);
+    }
+
+    let current: BTreeMap<String, Medians> =
+        results::by_syscall(measurements, |m| m.syscall_name.as_ref())
+            .into_iter()
+            .map(|(name, measurements)| (name, Medians::of(&measurements)))
+            .collect();
+
+    if check.update {
+        write_baseline(&check.baseline, &current);
+        println!("wrote {} syscalls to {}", current.len(), &check.baseline);
+        return;
+    }
+
+    let baseline = read_baseline(&check.baseline);
+
+    let mut failures = 0;
+    for (name, expected) in &baseline {
+        let actual = match current.get(name) {
+            Some(actual) => actual,
+            None => {
+                println!("warning: {} not reached by the corpus", name);
+                continue;
+            }
+        };
+
+        let mut moved = Vec::new();
+        if beyond(
+            expected.cpu_instruction_cost as f64,
+            actual.cpu_instruction_cost as f64,
+            check.tolerance,
+        ) {
+            moved.push(format!(
+                "CPU {}",
+                results::change(expected.cpu_instruction_cost, actual.cpu_instruction_cost)
+            ));
+        }
+        if beyond(
+            expected.memory_bytes_cost as f64,
+            actual.memory_bytes_cost as f64,
+            check.tolerance,
+        ) {
+            moved.push(format!(
+                "MEM {}",
+                results::change(expected.memory_bytes_cost, actual.memory_bytes_cost)
+            ));
+        }
+        if beyond(expected.cpu_per_ns, actual.cpu_per_ns, check.ns_tolerance) {
+            moved.push(format!(
+                "CPU/ns {:.3} -> {:.3}",
+                expected.cpu_per_ns, actual.cpu_per_ns
+            ));
+        }
+
+        if !moved.is_empty() {
+            failures += 1;
+            println!("FAIL {}\n    {}", name, moved.join("\n    "));
+        }
+    }
+
+    for name in current.keys().filter(|name| !baseline.contains_key(*name)) {
+        println!("warning: {} missing from the baseline", name);
+    }
+
+    if failures > 0 {
+        println!(
+            "{} of {} syscalls moved beyond tolerance; run `check --update` if intended",
+            failures,
+            baseline.len()
+        );
+        std::process::exit(1);
+    }
+    println!("{} syscalls within tolerance", baseline.len());
+}
+
+fn beyond(expected: f64, actual: f64, tolerance: f64) -> bool {
+    if expected == 0.0 {
+        return actual != 0.0;
+    }
+    ((actual - expected) / expected).abs() > tolerance
+}
+
+fn write_baseline(path: &str, medians: &BTreeMap<String, Medians>) {
+    let mut contents = format!("{}\n", Medians::HEADER);
+    for (name, m) in medians {
+        contents.push_str(&format!(
+            "{}|{}|{}|{}\n",
+            name, m.cpu_instruction_cost, m.memory_bytes_cost, m.cpu_per_ns
+        ));
+    }
+    fs::write(path, contents).unwrap();
+}
+
+fn read_baseline(path: &str) -> BTreeMap<String, Medians> {
+    let contents = fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "can't read {} ({}); create it with `check --update`",
+            path, e
+        )
+    });
+
+    contents
+        .lines()
+        .skip(1)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split('|').collect();
+            assert_eq!(fields.len(), 4, "malformed baseline line: {}", line);
+            let medians = Medians {
+                cpu_instruction_cost: fields[1].parse().unwrap(),
+                memory_bytes_cost: fields[2].parse().unwrap(),
+                cpu_per_ns: fields[3].parse().unwrap(),
+            };
+            (fields[0].to_string(), medians)
+        })
+        .collect()
+}
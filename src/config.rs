@@ -0,0 +1,328 @@
+use crate::auth::AuthMode;
+use crate::generator::Generator;
+use crate::limits::LimitConfig;
+use crate::storage::StorageConfig;
+use crate::sweep::SweepConfig;
+use crate::topology::TopologyConfig;
+use crate::valtree::ValTreeConfig;
+use crate::xdr::XdrConfig;
+use rand::Rng;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub iterations: u64,
+    pub workers: u64,
+    pub seed: u64,
+    pub output: String,
+    // Worker `i` is pinned to `pin_cores[i % pin_cores.len()]`.
+    pub pin_cores: Vec<usize>,
+    pub raise_priority: bool,
+    pub perf_counters: bool,
+    pub isolate_host: bool,
+    // Maximum number of steps per generated program in program mode.
+    pub program_len: Option<usize>,
+    // Seeds the contract's storage when `--storage-entries` is given.
+    pub storage: Option<StorageConfig>,
+    pub auth: AuthMode,
+    // Number of generated accounts in `signed` auth mode.
+    pub auth_accounts: u32,
+    // Registers extra contract instances when `--contracts` is given.
+    pub topology: Option<TopologyConfig>,
+    // WASM path and invocation spec path when profiling another contract.
+    pub custom_wasm: Option<(String, String)>,
+    // Runs under finite budget limits when any limit flag is given.
+    pub limits: Option<LimitConfig>,
+    // Steps the input size of one syscall when `--sweep` is given.
+    pub sweep: Option<SweepConfig>,
+    pub generator: Generator,
+    // Fixes the message length of the crypto generator.
+    pub message_len: Option<u32>,
+    // Fixes the first operand's bit width of the int256 generator.
+    pub int_bits: Option<u32>,
+    // Tree shape of the xdr generator.
+    pub xdr: XdrConfig,
+    // Tree shape and leaves of the val-tree generator.
+    pub val_tree: ValTreeConfig,
+    // Percent of rows per syscall, at each end of the CPU/ns range, to re-run
+    // with diagnostics after the run.
+    pub explain_outliers: Option<f64>,
+    pub outliers_dir: String,
+    // Writes rows for inputs that panicked, which are otherwise dropped.
+    pub keep_panics: bool,
+    pub track_alloc: bool,
+    // Syscalls allocating more than this multiple of their `MEM` charge are
+    // reported at the end of a `--track-alloc` run.
+    pub alloc_ratio: f64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            iterations: 10000,
+            workers: 1,
+            seed: rand::thread_rng().gen(),
+            output: "budget.csv".to_string(),
+            pin_cores: vec![],
+            raise_priority: false,
+            perf_counters: false,
+            isolate_host: false,
+            program_len: None,
+            storage: None,
+            auth: AuthMode::None,
+            auth_accounts: 4,
+            topology: None,
+            custom_wasm: None,
+            limits: None,
+            sweep: None,
+            generator: Generator::Guest,
+            message_len: None,
+            int_bits: None,
+            xdr: XdrConfig::default(),
+            val_tree: ValTreeConfig::default(),
+            explain_outliers: None,
+            outliers_dir: "outliers".to_string(),
+            keep_panics: false,
+            track_alloc: false,
+            alloc_ratio: 4.0,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_args() -> Config {
+        let mut config = Config::default();
+        let mut args = std::env::args().skip(1);
+        let mut wasm = None;
+        let mut spec = None;
+        let mut sweep_target = None;
+        let mut sweep_min = 1;
+        let mut sweep_max = 4096;
+        let mut sweep_factor = 2.0;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("missing value for {}", arg))
+            };
+            match arg.as_str() {
+                "--iterations" => config.iterations = value().parse().unwrap(),
+                "--workers" => config.workers = value().parse().unwrap(),
+                "--seed" => config.seed = value().parse().unwrap(),
+                "--output" => config.output = value(),
+                "--pin-cores" => {
+                    config.pin_cores = value()
+                        .split(',')
+                        .map(|core| core.trim().parse().unwrap())
+                        .collect()
+                }
+                "--priority" => config.raise_priority = true,
+                "--perf" => config.perf_counters = true,
+                "--isolate-host" => config.isolate_host = true,
+                "--program-len" => config.program_len = Some(value().parse().unwrap()),
+                "--storage-entries" => config.storage_mut().entries = value().parse().unwrap(),
+                "--storage-entry-size" => {
+                    config.storage_mut().entry_size = value().parse().unwrap()
+                }
+                "--storage-hit-rate" => config.storage_mut().hit_rate = value().parse().unwrap(),
+                "--auth" => config.auth = value().parse().unwrap(),
+                "--auth-accounts" => config.auth_accounts = value().parse().unwrap(),
+                "--contracts" => config.topology_mut().contracts = value().parse().unwrap(),
+                "--call-depth" => config.topology_mut().call_depth = value().parse().unwrap(),
+                "--call-hit-rate" => config.topology_mut().hit_rate = value().parse().unwrap(),
+                "--cpu-limit" => config.limits_mut().cpu = Some(value().parse().unwrap()),
+                "--mem-limit" => config.limits_mut().mem = Some(value().parse().unwrap()),
+                "--limit-scan" => config.limits_mut().scan = Some(value().parse().unwrap()),
+                "--sweep" => sweep_target = Some(value().parse().unwrap()),
+                "--sweep-min" => sweep_min = value().parse().unwrap(),
+                "--sweep-max" => sweep_max = value().parse().unwrap(),
+                "--sweep-factor" => sweep_factor = value().parse().unwrap(),
+                "--generator" => config.generator = value().parse().unwrap(),
+                "--message-len" => config.message_len = Some(value().parse().unwrap()),
+                "--int-bits" => config.int_bits = Some(value().parse().unwrap()),
+                "--xdr-depth" => config.xdr.depth = value().parse().unwrap(),
+                "--xdr-breadth" => config.xdr.breadth = value().parse().unwrap(),
+                "--xdr-deep-max" => config.xdr.deep_max = value().parse().unwrap(),
+                "--tree-depth" => config.val_tree.depth = value().parse().unwrap(),
+                "--tree-breadth" => config.val_tree.breadth = value().parse().unwrap(),
+                "--tree-leaves" => {
+                    config.val_tree.leaves = value()
+                        .split(',')
+                        .map(|leaf| leaf.parse().unwrap())
+                        .collect()
+                }
+                "--explain-outliers" => config.explain_outliers = Some(value().parse().unwrap()),
+                "--outliers-dir" => config.outliers_dir = value(),
+                "--keep-panics" => config.keep_panics = true,
+                "--track-alloc" => config.track_alloc = true,
+                "--alloc-ratio" => config.alloc_ratio = value().parse().unwrap(),
+                "--wasm" => wasm = Some(value()),
+                "--spec" => spec = Some(value()),
+                _ => panic!("unknown argument: {}", arg),
+            }
+        }
+
+        config.custom_wasm = match (wasm, spec) {
+            (Some(wasm), Some(spec)) => Some((wasm, spec)),
+            (None, None) => None,
+            _ => panic!("--wasm and --spec must be given together"),
+        };
+
+        config.sweep = sweep_target.map(|target| SweepConfig {
+            target,
+            min: sweep_min,
+            max: sweep_max,
+            factor: sweep_factor,
+        });
+        if let Some(sweep) = &config.sweep {
+            assert!(
+                sweep.min > 0 && sweep.min <= sweep.max,
+                "--sweep-min must be between 1 and --sweep-max"
+            );
+            assert!(sweep.factor > 1.0, "--sweep-factor must be above 1");
+            // Map keys are twice the size, and must fit a `u32`.
+            assert!(
+                sweep.max <= u32::MAX / 2,
+                "--sweep-max must be at most {}",
+                u32::MAX / 2
+            );
+            assert!(
+                config.program_len.is_none() && config.limits.is_none(),
+                "--sweep can't be combined with --program-len or budget limits"
+            );
+        }
+
+        // The other contract is called with its own arguments, so none of the
+        // ways of building or biasing fuzz contract inputs apply to it.
+        assert!(
+            config.custom_wasm.is_none()
+                || (config.generator == Generator::Guest
+                    && config.program_len.is_none()
+                    && config.sweep.is_none()
+                    && config.storage.is_none()
+                    && config.topology.is_none()
+                    && config.auth != AuthMode::Signed
+                    && !config.isolate_host),
+            "--wasm can't be combined with --generator, --program-len, --sweep, \
+             --storage-*, --contracts, --call-*, --auth signed or --isolate-host"
+        );
+        assert!(
+            config.generator == Generator::Guest
+                || (config.program_len.is_none() && config.sweep.is_none()),
+            "--program-len and --sweep only apply to the guest generator"
+        );
+        // Host functions are called without the VM, so there is no dispatch
+        // to separate.
+        assert!(
+            config.generator != Generator::Host || !config.isolate_host,
+            "--isolate-host doesn't apply to the host generator"
+        );
+        assert!(
+            config.generator == Generator::Crypto || config.message_len.is_none(),
+            "--message-len only applies to the crypto generator"
+        );
+        assert!(
+            config.generator == Generator::Int256 || config.int_bits.is_none(),
+            "--int-bits only applies to the int256 generator"
+        );
+        assert!(
+            config.int_bits.map_or(true, |bits| bits <= 256),
+            "--int-bits must be at most 256"
+        );
+        assert!(config.xdr.deep_max > 0, "--xdr-deep-max must be at least 1");
+        assert!(
+            !config.val_tree.leaves.is_empty(),
+            "--tree-leaves needs at least one kind"
+        );
+
+        if let Some(percent) = config.explain_outliers {
+            assert!(
+                percent > 0.0 && percent <= 50.0,
+                "--explain-outliers must be above 0 and at most 50"
+            );
+            // Rows are re-run from their seed alone.
+            assert!(
+                config.program_len.is_none() && config.limits.is_none() && config.sweep.is_none(),
+                "--explain-outliers only applies to plain per-seed runs"
+            );
+        }
+
+        assert!(config.workers > 0, "--workers must be at least 1");
+        // Resetting the RSS peak through `clear_refs` is process-wide.
+        assert!(
+            !config.track_alloc || config.workers == 1,
+            "--track-alloc needs --workers 1"
+        );
+        assert!(
+            config.auth_accounts > 0,
+            "--auth-accounts must be at least 1"
+        );
+        if let Some(storage) = &config.storage {
+            assert!(
+                (0.0..=1.0).contains(&storage.hit_rate),
+                "--storage-hit-rate must be between 0 and 1"
+            );
+        }
+
+        if let Some(topology) = &config.topology {
+            assert!(topology.call_depth > 0, "--call-depth must be at least 1");
+            assert!(
+                (0.0..=1.0).contains(&topology.hit_rate),
+                "--call-hit-rate must be between 0 and 1"
+            );
+        }
+
+        if let Some(limits) = &config.limits {
+            assert!(
+                limits.scan.map_or(true, |steps| steps >= 2),
+                "--limit-scan must be at least 2"
+            );
+            assert!(
+                config.program_len.is_none(),
+                "budget limits can't be combined with --program-len"
+            );
+        }
+
+        config
+    }
+
+    fn storage_mut(&mut self) -> &mut StorageConfig {
+        self.storage.get_or_insert(StorageConfig {
+            entries: 0,
+            entry_size: 64,
+            hit_rate: 0.8,
+        })
+    }
+
+    fn limits_mut(&mut self) -> &mut LimitConfig {
+        self.limits.get_or_insert(LimitConfig::default())
+    }
+
+    fn topology_mut(&mut self) -> &mut TopologyConfig {
+        self.topology.get_or_insert(TopologyConfig {
+            contracts: 2,
+            call_depth: 1,
+            hit_rate: 0.8,
+        })
+    }
+
+    pub fn core_for_worker(&self, index: u64) -> Option<usize> {
+        if self.pin_cores.is_empty() {
+            None
+        } else {
+            Some(self.pin_cores[index as usize % self.pin_cores.len()])
+        }
+    }
+
+    // Workers only stop competing for a core when each has one to itself.
+    pub fn is_contended(&self) -> bool {
+        if self.workers == 1 {
+            return false;
+        }
+        let mut cores: Vec<usize> = (0..self.workers)
+            .filter_map(|index| self.core_for_worker(index))
+            .collect();
+        cores.sort_unstable();
+        cores.dedup();
+        cores.len() as u64 != self.workers
+    }
+}
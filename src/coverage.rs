@@ -0,0 +1,84 @@
+// `coverage <results.csv>...`: lists how often each host function of the env
+// interface was reached by a campaign and how the calls ended, so functions
+// whose inputs never get past validation stand out. Guest (`syscalls::`)
+// and host (`host::`) rows are listed apart, but a function counts as
+// reached through either. Panics are only counted for runs made with
+// `--keep-panics`.
+
+use crate::host::HOST_FUNCTIONS;
+use crate::results;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Counts {
+    ok: u64,
+    error: u64,
+    budget_exceeded: u64,
+    panic: u64,
+}
+
+impl Counts {
+    fn failed(&self) -> u64 {
+        self.error + self.budget_exceeded + self.panic
+    }
+}
+
+pub fn run(args: impl Iterator<Item = String>) {
+    let paths: Vec<String> = args.collect();
+    assert!(!paths.is_empty(), "usage: coverage <results.csv>...");
+
+    let mut by_name: BTreeMap<String, Counts> = BTreeMap::new();
+    // Rows written before the `Outcome` column existed.
+    let mut unknown = 0;
+    for path in &paths {
+        for row in results::read(path) {
+            let counts = by_name.entry(row.syscall_name).or_default();
+            match row.outcome.as_deref() {
+                Some("ok") => counts.ok += 1,
+                Some("error") => counts.error += 1,
+                Some("budget-exceeded") => counts.budget_exceeded += 1,
+                Some("panic") => counts.panic += 1,
+                Some(other) => panic!("unknown outcome {} in {}", other, path),
+                None => unknown += 1,
+            }
+        }
+    }
+
+    println!("Function|Ok|Error|BudgetExceeded|Panic");
+    for (name, counts) in &by_name {
+        println!(
+            "{}|{}|{}|{}|{}",
+            name, counts.ok, counts.error, counts.budget_exceeded, counts.panic
+        );
+    }
+    if unknown > 0 {
+        println!("{} rows without an outcome were not counted", unknown);
+    }
+
+    let never_succeeded: Vec<&str> = by_name
+        .iter()
+        .filter(|(_, counts)| counts.ok == 0 && counts.failed() > 0)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !never_succeeded.is_empty() {
+        println!(
+            "\nreached but never succeeded ({}):\n  {}",
+            never_succeeded.len(),
+            never_succeeded.join("\n  ")
+        );
+    }
+
+    let never_reached: Vec<&str> = HOST_FUNCTIONS
+        .iter()
+        .filter(|function| {
+            !by_name.contains_key(function.name) && !by_name.contains_key(function.syscall)
+        })
+        .map(|function| function.syscall)
+        .collect();
+    println!(
+        "\nnever reached ({} of {}):\n  {}",
+        never_reached.len(),
+        HOST_FUNCTIONS.len(),
+        never_reached.join("\n  ")
+    );
+}
@@ -0,0 +1,133 @@
+// Valid and near-miss ed25519 and secp256k1 signatures for the crypto syscalls.
+
+use crate::fuzzcontract::{TypedFuzzInstruction, TypedModCrypto};
+use crate::generator::Generated;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use k256::ecdsa::{Signature, SigningKey};
+use rand::rngs::StdRng;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use soroban_sdk::{Bytes, Env};
+
+// Messages are drawn up to this length unless `--message-len` fixes it.
+const MAX_MESSAGE_LEN: u32 = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Case {
+    Valid,
+    // Signed over another message of the same length.
+    WrongMessage,
+    // Checked against (or recovered for) another key.
+    WrongKey,
+    // A valid signature with one bit flipped.
+    CorruptSignature,
+    // ECDSA only: the same signature with its `s` mirrored above n/2, which
+    // the host rejects.
+    HighS,
+}
+
+const CASES: [Case; 5] = [
+    Case::Valid,
+    Case::WrongMessage,
+    Case::WrongKey,
+    Case::CorruptSignature,
+    Case::HighS,
+];
+
+pub fn generate(env: &Env, rng: &mut StdRng, message_len: Option<u32>) -> Generated {
+    let len = message_len.unwrap_or_else(|| rng.gen_range(0..=MAX_MESSAGE_LEN));
+    let message = random_bytes(rng, len as usize);
+
+    if rng.gen() {
+        // Leaves out `HighS`, which is last.
+        let case = CASES[rng.gen_range(0..CASES.len() - 1)];
+        Generated {
+            instruction: ed25519(env, rng, &message, case),
+            description: format!("ed25519 {:?} message_len={}", case, len),
+            size: Some(len as u64),
+        }
+    } else {
+        let case = CASES[rng.gen_range(0..CASES.len())];
+        Generated {
+            instruction: secp256k1(env, rng, &message, case),
+            description: format!("secp256k1 {:?} message_len={}", case, len),
+            size: Some(len as u64),
+        }
+    }
+}
+
+fn ed25519(env: &Env, rng: &mut StdRng, message: &[u8], case: Case) -> TypedFuzzInstruction {
+    let keypair = ed25519_keypair(rng);
+
+    let signed = match case {
+        Case::WrongMessage => random_bytes(rng, message.len()),
+        _ => message.to_vec(),
+    };
+    let mut signature = keypair.sign(&signed).to_bytes();
+    if case == Case::CorruptSignature {
+        flip_bit(rng, &mut signature);
+    }
+    let public = match case {
+        Case::WrongKey => ed25519_keypair(rng).public,
+        _ => keypair.public,
+    };
+
+    TypedFuzzInstruction::Crypto(TypedModCrypto::VerifySigEd25519(
+        Bytes::from_slice(env, public.as_bytes()),
+        Bytes::from_slice(env, message),
+        Bytes::from_slice(env, &signature),
+    ))
+}
+
+// The syscall recovers the key from a 32-byte digest; the message only
+// determines the digest, so its length doesn't change the cost here.
+fn secp256k1(env: &Env, rng: &mut StdRng, message: &[u8], case: Case) -> TypedFuzzInstruction {
+    let key = SigningKey::random(rng);
+
+    let digest = Sha256::digest(message);
+    let signed = match case {
+        Case::WrongMessage => Sha256::digest(random_bytes(rng, message.len())),
+        _ => digest,
+    };
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&signed).unwrap();
+
+    let signature = match case {
+        Case::HighS => {
+            let (r, s) = signature.split_scalars();
+            Signature::from_scalars(r.to_bytes(), (-*s).to_bytes()).unwrap()
+        }
+        _ => signature,
+    };
+    let mut signature = signature.to_bytes();
+    if case == Case::CorruptSignature {
+        flip_bit(rng, &mut signature);
+    }
+    // Recovery still succeeds with the other parity, but yields another key.
+    let recovery_id = match case {
+        Case::WrongKey => recovery_id.to_byte() ^ 1,
+        _ => recovery_id.to_byte(),
+    };
+
+    TypedFuzzInstruction::Crypto(TypedModCrypto::RecoverKeyEcdsaSecp256k1(
+        Bytes::from_slice(env, &digest),
+        Bytes::from_slice(env, &signature),
+        recovery_id as u32,
+    ))
+}
+
+fn ed25519_keypair(rng: &mut StdRng) -> Keypair {
+    let secret = SecretKey::from_bytes(&rng.gen::<[u8; 32]>()).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+fn random_bytes(rng: &mut StdRng, len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    rng.fill(&mut bytes[..]);
+    bytes
+}
+
+fn flip_bit(rng: &mut StdRng, bytes: &mut [u8]) {
+    let bit = rng.gen_range(0..bytes.len() * 8);
+    bytes[bit / 8] ^= 1 << (bit % 8);
+}
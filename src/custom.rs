@@ -0,0 +1,160 @@
+// Profiles an arbitrary contract WASM loaded at runtime, described by a JSON
+// invocation spec:
+//
+//     { "invocations": [ { "function": "transfer", "args": ["Address", "Address", "i128"] } ] }
+//
+// Each iteration picks one invocation and generates its arguments from the
+// `SorobanArbitrary` prototypes of the listed types.
+
+use serde::Deserialize;
+use soroban_sdk::arbitrary::arbitrary::{self, Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::{Address, Bytes, Env, FromVal, IntoVal, Map, String, Symbol, Val, Vec};
+use std::fs;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum ArgType {
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "u32")]
+    U32,
+    #[serde(rename = "i32")]
+    I32,
+    #[serde(rename = "u64")]
+    U64,
+    #[serde(rename = "i64")]
+    I64,
+    #[serde(rename = "u128")]
+    U128,
+    #[serde(rename = "i128")]
+    I128,
+    Address,
+    Bytes,
+    String,
+    Symbol,
+    Val,
+    #[serde(rename = "Vec<Val>")]
+    VecVal,
+    #[serde(rename = "Map<Val,Val>")]
+    MapValVal,
+}
+
+impl ArgType {
+    // Returns the argument along with a description of the prototype it was
+    // built from.
+    fn arbitrary_arg(
+        &self,
+        env: &Env,
+        u: &mut Unstructured,
+    ) -> arbitrary::Result<(Val, std::string::String)> {
+        fn object<T>(
+            env: &Env,
+            u: &mut Unstructured,
+        ) -> arbitrary::Result<(Val, std::string::String)>
+        where
+            T: SorobanArbitrary,
+            T::Prototype: std::fmt::Debug,
+        {
+            let prototype = T::Prototype::arbitrary(u)?;
+            let description = format!("{:?}", prototype);
+            Ok((T::from_val(env, &prototype).into_val(env), description))
+        }
+
+        fn primitive<T>(
+            env: &Env,
+            u: &mut Unstructured,
+        ) -> arbitrary::Result<(Val, std::string::String)>
+        where
+            T: for<'a> Arbitrary<'a> + IntoVal<Env, Val> + std::fmt::Debug,
+        {
+            let value = T::arbitrary(u)?;
+            let description = format!("{:?}", value);
+            Ok((value.into_val(env), description))
+        }
+
+        match self {
+            ArgType::Bool => primitive::<bool>(env, u),
+            ArgType::U32 => primitive::<u32>(env, u),
+            ArgType::I32 => primitive::<i32>(env, u),
+            ArgType::U64 => primitive::<u64>(env, u),
+            ArgType::I64 => primitive::<i64>(env, u),
+            ArgType::U128 => primitive::<u128>(env, u),
+            ArgType::I128 => primitive::<i128>(env, u),
+            ArgType::Address => object::<Address>(env, u),
+            ArgType::Bytes => object::<Bytes>(env, u),
+            ArgType::String => object::<String>(env, u),
+            ArgType::Symbol => object::<Symbol>(env, u),
+            ArgType::Val => object::<Val>(env, u),
+            ArgType::VecVal => object::<Vec<Val>>(env, u),
+            ArgType::MapValVal => object::<Map<Val, Val>>(env, u),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InvocationSpec {
+    pub function: std::string::String,
+    pub args: std::vec::Vec<ArgType>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ContractSpec {
+    pub invocations: std::vec::Vec<InvocationSpec>,
+}
+
+pub struct CustomCall {
+    pub name: std::string::String,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    pub description: std::string::String,
+}
+
+pub struct CustomContract {
+    pub contract_id: Address,
+    invocations: std::vec::Vec<(std::string::String, InvocationSpec)>,
+}
+
+impl CustomContract {
+    pub fn load(env: &Env, wasm_path: &str, spec_path: &str) -> CustomContract {
+        let wasm = fs::read(wasm_path).unwrap();
+        let spec: ContractSpec =
+            serde_json::from_str(&fs::read_to_string(spec_path).unwrap()).unwrap();
+        assert!(
+            !spec.invocations.is_empty(),
+            "{} has no invocations",
+            spec_path
+        );
+
+        let contract_id = env.register_contract_wasm(None, wasm.as_slice());
+
+        let invocations = spec
+            .invocations
+            .into_iter()
+            .map(|invocation| (format!("contract::{}", invocation.function), invocation))
+            .collect();
+
+        CustomContract {
+            contract_id,
+            invocations,
+        }
+    }
+
+    pub fn arbitrary_call(&self, env: &Env, u: &mut Unstructured) -> arbitrary::Result<CustomCall> {
+        let (name, invocation) = u.choose(&self.invocations)?;
+
+        let mut args = Vec::new(env);
+        let mut descriptions = std::vec::Vec::new();
+        for arg_type in &invocation.args {
+            let (arg, description) = arg_type.arbitrary_arg(env, u)?;
+            args.push_back(arg);
+            descriptions.push(description);
+        }
+
+        Ok(CustomCall {
+            name: name.clone(),
+            function: Symbol::new(env, &invocation.function),
+            args,
+            description: format!("{}({})", invocation.function, descriptions.join(", ")),
+        })
+    }
+}
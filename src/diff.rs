@@ -0,0 +1,185 @@
+// `diff <before.csv> <after.csv>`: compares two runs over the same seeds,
+// typically from binaries built against different host revisions. The seeds
+// are the corpus: both runs need the same `--seed`, `--iterations` and mode
+// flags, and rows are matched by seed (and program step, size or limit).
+
+use crate::results::{self, Row};
+use std::collections::HashMap;
+
+pub fn run(mut args: impl Iterator<Item = String>) {
+    let usage = "usage: diff <before.csv> <after.csv>";
+    let before_path = args.next().expect(usage);
+    let after_path = args.next().expect(usage);
+    assert!(args.next().is_none(), "{}", usage);
+
+    let before = results::read(&before_path);
+    let after = results::read(&after_path);
+
+    let Pairing {
+        pairs,
+        mismatched,
+        missing,
+    } = pair(&before, &after);
+
+    println!(
+        "{} inputs compared, {} only in {}, {} only in {}",
+        pairs.len(),
+        missing,
+        before_path,
+        after.len().saturating_sub(pairs.len() + mismatched),
+        after_path
+    );
+    if mismatched > 0 {
+        println!(
+            "warning: {} seeds generated different inputs; the runs don't share a corpus",
+            mismatched
+        );
+    }
+
+    let charge_changed = |(before, after): &(&Row, &Row)| {
+        before.cpu_instruction_cost != after.cpu_instruction_cost
+            || before.memory_bytes_cost != after.memory_bytes_cost
+    };
+
+    println!();
+    println!("median per syscall (* = some input's charge changed):");
+    let groups = results::by_syscall(pairs.iter().copied(), |(row, _)| &row.syscall_name);
+    for (name, pairs) in groups {
+        let changed = pairs.iter().filter(|&pair| charge_changed(pair)).count();
+        println!(
+            "{} {} ({} inputs, {} changed)\n    CPU {}\n    MEM {}\n    Duration {}",
+            if changed > 0 { "*" } else { " " },
+            name,
+            pairs.len(),
+            changed,
+            median_change(&pairs, |row| row.cpu_instruction_cost),
+            median_change(&pairs, |row| row.memory_bytes_cost),
+            median_change(&pairs, |row| row.duration),
+        );
+    }
+
+    let changed: Vec<_> = pairs.iter().filter(|&pair| charge_changed(pair)).collect();
+    if changed.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("inputs whose charge changed:");
+    for (before, after) in changed {
+        println!(
+            "  seed {}{}{}{} {}\n    CPU {}\n    MEM {}\n    input: {}",
+            before.seed,
+            before
+                .program_step
+                .map(|step| format!(" step {}", step))
+                .unwrap_or_default(),
+            before
+                .size
+                .map(|size| format!(" size {}", size))
+                .unwrap_or_default(),
+            before
+                .limit
+                .map(|(cpu, mem)| format!(" limit {}/{}", cpu, mem))
+                .unwrap_or_default(),
+            before.syscall_name,
+            results::change(before.cpu_instruction_cost, after.cpu_instruction_cost),
+            results::change(before.memory_bytes_cost, after.memory_bytes_cost),
+            before.input,
+        );
+    }
+}
+
+pub struct Pairing<'a> {
+    // Rows of the same input in both runs, before first.
+    pub pairs: Vec<(&'a Row, &'a Row)>,
+    // Rows whose key is in both runs, but with different inputs.
+    pub mismatched: usize,
+    // Rows only in the first run.
+    pub missing: usize,
+}
+
+pub fn pair<'a>(before: &'a [Row], after: &'a [Row]) -> Pairing<'a> {
+    let after_by_key: HashMap<_, _> = after.iter().map(|row| (row.key(), row)).collect();
+
+    let mut pairing = Pairing {
+        pairs: Vec::new(),
+        mismatched: 0,
+        missing: 0,
+    };
+    for row in before {
+        match after_by_key.get(&row.key()) {
+            Some(other) if other.input == row.input => pairing.pairs.push((row, other)),
+            Some(_) => pairing.mismatched += 1,
+            None => pairing.missing += 1,
+        }
+    }
+    pairing
+}
+
+fn median_change(pairs: &[(&Row, &Row)], value: fn(&Row) -> u64) -> String {
+    results::change(
+        results::median(pairs.iter().map(|(before, _)| value(before))),
+        results::median(pairs.iter().map(|(_, after)| value(after))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::Measurement;
+    use crate::sweep::SweepTarget;
+    use std::fs;
+
+    // A sweep run of one seed: a row per size, with the given CPU charges.
+    fn sweep_csv(cpu: &[u64]) -> String {
+        let mut csv = format!("{}\n", Measurement::CSV_HEADER);
+        for (index, cpu) in cpu.iter().enumerate() {
+            let size = 1u64 << index;
+            let row: Vec<String> = Measurement::CSV_HEADER
+                .split('|')
+                .map(|column| match column {
+                    "Syscalls" => format!("\"{}\"", SweepTarget::BytesNewFromLinearMemory.name()),
+                    "SyscallsInput" => format!("\"Buf(BytesNewFromLinearMemory(0, {}))\"", size),
+                    "ArbitraryInput" => "\"\"".to_string(),
+                    "CPU" => cpu.to_string(),
+                    "MEM" => (size * 8).to_string(),
+                    "Duration" => "1000".to_string(),
+                    "Seed" => "7".to_string(),
+                    "Outcome" => "ok".to_string(),
+                    "Size" => size.to_string(),
+                    _ => String::new(),
+                })
+                .collect();
+            csv.push_str(&row.join("|"));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    #[test]
+    fn sweep_rows_pair_by_size() {
+        let dir = std::env::temp_dir();
+        let before_path = dir.join(format!("test-budget-{}-before.csv", std::process::id()));
+        let after_path = dir.join(format!("test-budget-{}-after.csv", std::process::id()));
+        fs::write(&before_path, sweep_csv(&[100, 200, 400, 800])).unwrap();
+        fs::write(&after_path, sweep_csv(&[100, 250, 400, 800])).unwrap();
+
+        let before = results::read(before_path.to_str().unwrap());
+        let after = results::read(after_path.to_str().unwrap());
+        fs::remove_file(&before_path).unwrap();
+        fs::remove_file(&after_path).unwrap();
+
+        let pairing = pair(&before, &after);
+        assert_eq!(pairing.pairs.len(), 4);
+        assert_eq!(pairing.mismatched, 0);
+        assert_eq!(pairing.missing, 0);
+
+        let changed: Vec<_> = pairing
+            .pairs
+            .iter()
+            .filter(|(before, after)| before.cpu_instruction_cost != after.cpu_instruction_cost)
+            .map(|(before, _)| before.size)
+            .collect();
+        assert_eq!(changed, vec![Some(2)]);
+    }
+}
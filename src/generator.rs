@@ -0,0 +1,48 @@
+use crate::fuzzcontract::TypedFuzzInstruction;
+use std::str::FromStr;
+
+// Where the inputs of a run come from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Generator {
+    // Arbitrary `TypedFuzzInstruction`s run through the fuzz contract.
+    Guest,
+    // Every host function of the env interface, called on the host directly.
+    Host,
+    // Genuine and near-miss signatures for the signature syscalls.
+    Crypto,
+    // Serialized `ScVal` trees for `DeserializeFromBytes`.
+    Xdr,
+    // Nested `Vec`/`Map` trees for the syscalls that visit objects.
+    ValTree,
+    // In-bounds positions and lengths for the linear memory syscalls.
+    LinearMemory,
+    // 256-bit integer objects of chosen widths for the arithmetic syscalls.
+    Int256,
+}
+
+impl FromStr for Generator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Generator, String> {
+        match s {
+            "guest" => Ok(Generator::Guest),
+            "host" => Ok(Generator::Host),
+            "crypto" => Ok(Generator::Crypto),
+            "xdr" => Ok(Generator::Xdr),
+            "val-tree" => Ok(Generator::ValTree),
+            "linear-memory" => Ok(Generator::LinearMemory),
+            "int256" => Ok(Generator::Int256),
+            _ => Err(format!("unknown generator: {}", s)),
+        }
+    }
+}
+
+// An input of the generators that build guest instructions directly rather
+// than through prototypes.
+pub struct Generated {
+    pub instruction: TypedFuzzInstruction,
+    // For the `ArbitraryInput` column.
+    pub description: String,
+    // Message length, node count or the like, for the `Size` column.
+    pub size: Option<u64>,
+}
@@ -0,0 +1,156 @@
+// Host functions generated from the env interface in soroban-env-common, so
+// every function the host defines is measured without adding it to the
+// guest or the `syscalls!` table. They are called on the host directly:
+// charges cover the function itself, without VM dispatch, and functions
+// that need linear memory fail for lack of a VM.
+//
+// Arguments are generated from their interface types. Rows are named
+// `host::<module>::<function>`, apart from the guest's `syscalls::` rows,
+// whose charges include the VM dispatch.
+//
+// The guest's prototypes aren't generated here: its `TypedMod*` enums come
+// from the fuzz contract's WASM, so a function it has no variant for has
+// nothing to convert to. The `syscalls` test checks the guest's table
+// against the same interface instead.
+
+use crate::measurement::Outcome;
+use soroban_env_common::xdr::ScErrorType;
+use soroban_env_common::Env as HostEnv;
+use soroban_env_common::{
+    call_macro_with_all_host_functions, AddressObject, Bool, BytesObject, DurationObject, Error,
+    I128Object, I256Object, I256Val, I32Val, I64Object, I64Val, MapObject, StorageType,
+    StringObject, Symbol, SymbolObject, TimepointObject, U128Object, U256Object, U256Val, U32Val,
+    U64Object, U64Val, Val, VecObject, Void,
+};
+use soroban_sdk::arbitrary::arbitrary::{self, Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::env::internal::{Host, HostError};
+use soroban_sdk::{Address, Bytes, Env, FromVal, IntoVal, Map, String};
+
+trait HostArg: Sized {
+    fn arbitrary(env: &Env, u: &mut Unstructured) -> arbitrary::Result<Self>;
+}
+
+// Generates a value of the SDK type and converts it through `Val`. Inputs
+// whose value doesn't fit the argument type (e.g. a small `u64` for a
+// `U64Object`) are skipped like any other unusable input.
+macro_rules! sdk_args {
+    ($($host:ty => $sdk:ty),* $(,)?) => {
+        $(
+            impl HostArg for $host {
+                fn arbitrary(env: &Env, u: &mut Unstructured) -> arbitrary::Result<Self> {
+                    let prototype = <$sdk as SorobanArbitrary>::Prototype::arbitrary(u)?;
+                    let val: Val = <$sdk>::from_val(env, &prototype).into_val(env);
+                    <$host>::try_from(val).map_err(|_| arbitrary::Error::IncorrectFormat)
+                }
+            }
+        )*
+    };
+}
+
+// The 256-bit, time and error types have no SDK counterpart here, so like
+// the guest's prototypes they come from arbitrary `Val`s.
+sdk_args! {
+    AddressObject => Address,
+    Bool => bool,
+    BytesObject => Bytes,
+    DurationObject => Val,
+    Error => Val,
+    I128Object => i128,
+    I256Object => Val,
+    I256Val => Val,
+    I32Val => i32,
+    I64Object => i64,
+    I64Val => i64,
+    MapObject => Map<Val, Val>,
+    StringObject => String,
+    Symbol => soroban_sdk::Symbol,
+    SymbolObject => soroban_sdk::Symbol,
+    TimepointObject => Val,
+    U128Object => u128,
+    U256Object => Val,
+    U256Val => Val,
+    U32Val => u32,
+    U64Object => u64,
+    U64Val => u64,
+    VecObject => soroban_sdk::Vec<Val>,
+    Void => Val,
+}
+
+impl HostArg for Val {
+    fn arbitrary(env: &Env, u: &mut Unstructured) -> arbitrary::Result<Self> {
+        let prototype = <Val as SorobanArbitrary>::Prototype::arbitrary(u)?;
+        Ok(Val::from_val(env, &prototype))
+    }
+}
+
+impl HostArg for StorageType {
+    fn arbitrary(_env: &Env, u: &mut Unstructured) -> arbitrary::Result<Self> {
+        Ok(*u.choose(&[
+            StorageType::Temporary,
+            StorageType::Persistent,
+            StorageType::Instance,
+        ])?)
+    }
+}
+
+pub struct HostCall {
+    pub args: std::string::String,
+    invoke: Box<dyn FnOnce(&Host) -> Result<(), HostError>>,
+}
+
+impl HostCall {
+    pub fn invoke(self, host: &Host) -> Outcome {
+        match (self.invoke)(host) {
+            Ok(()) => Outcome::Ok,
+            Err(e) if e.error.is_type(ScErrorType::Budget) => Outcome::BudgetExceeded,
+            Err(_) => Outcome::Error,
+        }
+    }
+}
+
+pub struct HostFunction {
+    // Name of the rows of this generator.
+    pub name: &'static str,
+    // Name of the guest instruction calling the same function.
+    pub syscall: &'static str,
+    pub arbitrary: fn(&Env, &mut Unstructured) -> arbitrary::Result<HostCall>,
+}
+
+macro_rules! host_functions {
+    {
+        $(
+            $(#[$mod_attr:meta])*
+            mod $mod_id:ident $mod_str:literal
+            {
+                $(
+                    $(#[$fn_attr:meta])*
+                    { $fn_str:literal, fn $fn_id:ident ($($arg:ident: $type:ty),*) -> $ret:ty }
+                )*
+            }
+        )*
+    } => {
+        pub const HOST_FUNCTIONS: &[HostFunction] = &[
+            $(
+                $(
+                    HostFunction {
+                        name: concat!("host::", stringify!($mod_id), "::", stringify!($fn_id)),
+                        syscall: concat!("syscalls::", stringify!($mod_id), "::", stringify!($fn_id)),
+                        // Underscored because nullary functions don't use them.
+                        arbitrary: |_env: &Env, _u: &mut Unstructured| {
+                            $( let $arg = <$type as HostArg>::arbitrary(_env, _u)?; )*
+                            Ok(HostCall {
+                                args: format!("{:?}", ($(&$arg,)*)),
+                                invoke: Box::new(move |host: &Host| {
+                                    <Host as HostEnv>::$fn_id(host, $($arg),*).map(|_| ())
+                                }),
+                            })
+                        },
+                    },
+                )*
+            )*
+        ];
+    };
+}
+
+call_macro_with_all_host_functions! { host_functions }
@@ -0,0 +1,210 @@
+// 256-bit operands of chosen widths for the `U256*`/`I256*` syscalls.
+
+use crate::fuzzcontract::{FakeVal, TypedFuzzInstruction, TypedModInt};
+use crate::generator::Generated;
+use rand::rngs::StdRng;
+use rand::Rng;
+use soroban_sdk::{Env, IntoVal, Val, I256, U256};
+
+// Most significant first, as taken by `from_parts`.
+type Limbs = [u64; 4];
+
+const U256_MAX: Limbs = [u64::MAX; 4];
+const I256_MAX: Limbs = [i64::MAX as u64, u64::MAX, u64::MAX, u64::MAX];
+const I256_MIN: Limbs = [1 << 63, 0, 0, 0];
+
+// How far from the overflow boundary the adjacent cases start.
+const ADJACENT: u64 = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Shl,
+    Shr,
+}
+
+const OPS: [Op; 7] = [
+    Op::Add,
+    Op::Sub,
+    Op::Mul,
+    Op::Div,
+    Op::Pow,
+    Op::Shl,
+    Op::Shr,
+];
+
+// `bits` fixes the width of the first operand (the magnitude for signed
+// ones); otherwise widths are drawn per input.
+pub fn generate(env: &Env, rng: &mut StdRng, bits: Option<u32>) -> Generated {
+    let op = OPS[rng.gen_range(0..OPS.len())];
+    let signed = rng.gen();
+    let adjacent = rng.gen_bool(0.25);
+    let max_bits = if signed { 255 } else { 256 };
+    let a_bits = bits
+        .unwrap_or_else(|| rng.gen_range(0..=max_bits))
+        .min(max_bits);
+
+    let (a, b, arg) = match (op, adjacent) {
+        // Crosses the maximum about half the time.
+        (Op::Add, true) => {
+            let max = if signed { I256_MAX } else { U256_MAX };
+            let k = rng.gen_range(0..ADJACENT);
+            (minus(max, k), small(rng, 2 * k + 2), 0)
+        }
+        // Crosses zero (unsigned) or the minimum (signed) about half the time.
+        (Op::Sub, true) if signed => {
+            let k = rng.gen_range(0..ADJACENT);
+            (plus(I256_MIN, k), small(rng, 2 * k + 2), 0)
+        }
+        (Op::Sub, true) => {
+            let k = rng.gen_range(0..ADJACENT);
+            (small(rng, k + 1), small(rng, 2 * k + 2), 0)
+        }
+        // Widths summing to just around the limit.
+        (Op::Mul, true) => {
+            let a_bits = a_bits.max(1);
+            let b_bits = (max_bits + 1).saturating_sub(a_bits) + rng.gen_range(0..=1);
+            let b = with_bits(rng, b_bits.min(max_bits));
+            (with_bits(rng, a_bits), b, 0)
+        }
+        (Op::Add | Op::Sub | Op::Mul, _) => {
+            let b_bits = rng.gen_range(0..=max_bits);
+            (with_bits(rng, a_bits), with_bits(rng, b_bits), 0)
+        }
+        // Zero divisors are left to the arbitrary inputs.
+        (Op::Div, _) => {
+            let b_bits = rng.gen_range(1..=max_bits);
+            (with_bits(rng, a_bits), with_bits(rng, b_bits), 0)
+        }
+        // The exponent that just overflows the base's width.
+        (Op::Pow, true) => {
+            let a_bits = a_bits.max(2);
+            let exponent = max_bits / (a_bits - 1) + rng.gen_range(0..=1);
+            (with_bits(rng, a_bits), [0; 4], exponent)
+        }
+        // Bases of 0 and 1 never overflow, whatever the exponent.
+        (Op::Pow, false) => {
+            let base = small(rng, 2);
+            let exponent_bits = rng.gen_range(0..32);
+            let exponent = rng.gen_range(0..=u32::MAX >> exponent_bits);
+            (base, [0; 4], exponent)
+        }
+        (Op::Shl | Op::Shr, _) => (with_bits(rng, a_bits), [0; 4], rng.gen_range(0..=255)),
+    };
+
+    // Either sign, so both the positive and negative paths are covered.
+    let (a, b) = if signed {
+        let a = if rng.gen() && !adjacent { negate(a) } else { a };
+        let b = if rng.gen() && !adjacent { negate(b) } else { b };
+        (a, b)
+    } else {
+        (a, b)
+    };
+
+    let val = |limbs: Limbs| -> FakeVal {
+        let val: Val = if signed {
+            I256::from_parts(env, limbs[0] as i64, limbs[1], limbs[2], limbs[3]).into_val(env)
+        } else {
+            U256::from_parts(env, limbs[0], limbs[1], limbs[2], limbs[3]).into_val(env)
+        };
+        FakeVal(val.get_payload())
+    };
+
+    let instruction = match (op, signed) {
+        (Op::Add, false) => TypedModInt::U256Add(val(a), val(b)),
+        (Op::Add, true) => TypedModInt::I256Add(val(a), val(b)),
+        (Op::Sub, false) => TypedModInt::U256Sub(val(a), val(b)),
+        (Op::Sub, true) => TypedModInt::I256Sub(val(a), val(b)),
+        (Op::Mul, false) => TypedModInt::U256Mul(val(a), val(b)),
+        (Op::Mul, true) => TypedModInt::I256Mul(val(a), val(b)),
+        (Op::Div, false) => TypedModInt::U256Div(val(a), val(b)),
+        (Op::Div, true) => TypedModInt::I256Div(val(a), val(b)),
+        (Op::Pow, false) => TypedModInt::U256Pow(val(a), arg),
+        (Op::Pow, true) => TypedModInt::I256Pow(val(a), arg),
+        (Op::Shl, false) => TypedModInt::U256Shl(val(a), arg),
+        (Op::Shl, true) => TypedModInt::I256Shl(val(a), arg),
+        (Op::Shr, false) => TypedModInt::U256Shr(val(a), arg),
+        (Op::Shr, true) => TypedModInt::I256Shr(val(a), arg),
+    };
+
+    Generated {
+        instruction: TypedFuzzInstruction::Int(instruction),
+        description: format!(
+            "{:?} signed={} adjacent={} a_bits={} b_bits={} arg={}",
+            op,
+            signed,
+            adjacent,
+            width(magnitude(a, signed)),
+            width(magnitude(b, signed)),
+            arg
+        ),
+        size: Some(width(magnitude(a, signed)) as u64),
+    }
+}
+
+// A value of exactly `bits` significant bits.
+fn with_bits(rng: &mut StdRng, bits: u32) -> Limbs {
+    let mut limbs: Limbs = rng.gen();
+    for (index, limb) in limbs.iter_mut().enumerate() {
+        let lowest = (3 - index as u32) * 64;
+        if bits <= lowest {
+            *limb = 0;
+        } else if bits - lowest < 64 {
+            *limb &= (1 << (bits - lowest)) - 1;
+        }
+    }
+    if bits > 0 {
+        let top = bits - 1;
+        limbs[3 - (top / 64) as usize] |= 1 << (top % 64);
+    }
+    limbs
+}
+
+fn small(rng: &mut StdRng, below: u64) -> Limbs {
+    [0, 0, 0, rng.gen_range(0..below)]
+}
+
+fn plus(limbs: Limbs, k: u64) -> Limbs {
+    let mut result = limbs;
+    let mut carry = k;
+    for limb in result.iter_mut().rev() {
+        let (sum, overflow) = limb.overflowing_add(carry);
+        *limb = sum;
+        carry = overflow as u64;
+    }
+    result
+}
+
+fn minus(limbs: Limbs, k: u64) -> Limbs {
+    let mut result = limbs;
+    let mut borrow = k;
+    for limb in result.iter_mut().rev() {
+        let (difference, overflow) = limb.overflowing_sub(borrow);
+        *limb = difference;
+        borrow = overflow as u64;
+    }
+    result
+}
+
+// Two's complement.
+fn negate(limbs: Limbs) -> Limbs {
+    plus(limbs.map(|limb| !limb), 1)
+}
+
+fn magnitude(limbs: Limbs, signed: bool) -> Limbs {
+    if signed && limbs[0] >> 63 == 1 {
+        negate(limbs)
+    } else {
+        limbs
+    }
+}
+
+fn width(limbs: Limbs) -> u32 {
+    limbs.iter().position(|limb| *limb != 0).map_or(0, |index| {
+        (4 - index as u32) * 64 - limbs[index].leading_zeros()
+    })
+}
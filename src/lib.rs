@@ -0,0 +1,96 @@
+// Budget measurement for soroban host functions, driven through the fuzz
+// contract. The `test-budget` binary runs campaigns from the command line;
+// other crates can embed the same measurements with `Harness` or `run`.
+//
+// Allocation tracking only counts when the final binary installs
+// `alloc::CountingAllocator` as its global allocator.
+//
+// Measuring goes through the SDK's test `Env`, so the SDK is always built
+// with its `testutils` feature. Nothing is printed: problems come back in the
+// measurements and from `run`, and the binary reports them.
+
+use crate::config::Config;
+use crate::measurement::Measurement;
+use crate::syscalls::TypedFuzzInstructionPrototype;
+use crate::worker::Worker;
+use soroban_sdk::Env;
+
+pub mod alloc;
+pub mod auth;
+pub mod check;
+pub mod config;
+pub mod coverage;
+pub mod crypto;
+pub mod custom;
+pub mod diff;
+pub mod generator;
+pub mod host;
+pub mod int256;
+pub mod limits;
+pub mod linmem;
+pub mod measurement;
+pub mod noise;
+pub mod outliers;
+pub mod perf;
+pub mod program;
+pub mod results;
+pub mod scaling;
+pub mod storage;
+pub mod sweep;
+pub mod syscalls;
+pub mod topology;
+pub mod valtree;
+pub mod worker;
+pub mod xdr;
+
+pub mod fuzzcontract {
+    soroban_sdk::contractimport!(
+        file = "../contract-for-fuzz/target/wasm32-unknown-unknown/release/contract_for_fuzz.wasm"
+    );
+}
+
+// Measures single inputs in an `Env` owned by the caller, which gets the fuzz
+// contract registered once.
+pub struct Harness {
+    worker: Worker,
+}
+
+impl Harness {
+    pub fn new(env: &Env) -> Harness {
+        Harness::with_config(env, &Config::default())
+    }
+
+    // Takes the biasing options (storage, topology, mocked auth) and
+    // `--perf`/`--track-alloc` from `config`; run modes are ignored.
+    pub fn with_config(env: &Env, config: &Config) -> Harness {
+        Harness {
+            worker: Worker::with_env(config, env.clone()),
+        }
+    }
+
+    // Charges cover the whole invocation, including argument conversion, as
+    // in a plain run. Panics are returned with `Outcome::Panic`.
+    pub fn measure(&self, input: &TypedFuzzInstructionPrototype) -> Measurement {
+        self.worker.measure_one(input)
+    }
+}
+
+// Registers the fuzz contract on every call; use a `Harness` to measure
+// several inputs in the same `Env`.
+pub fn measure(env: &Env, input: &TypedFuzzInstructionPrototype) -> Measurement {
+    Harness::new(env).measure(input)
+}
+
+// Runs the campaign described by `config` on its workers, handing each
+// measurement to `sink` in the order they arrive. Returns the workers'
+// warnings about pinning, priority and hardware counters.
+pub fn run(config: &Config, mut sink: impl FnMut(Measurement)) -> Vec<String> {
+    let (rx, handles) = worker::spawn_all(config);
+    for measurement in rx {
+        sink(measurement);
+    }
+    handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect()
+}
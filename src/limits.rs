@@ -0,0 +1,199 @@
+// Runs inputs under finite budget limits instead of `reset_unlimited`, to
+// check that syscalls stop with a budget error once the limit is crossed.
+
+use crate::measurement::{Measurement, Outcome};
+
+#[derive(Clone, Debug, Default)]
+pub struct LimitConfig {
+    pub cpu: Option<u64>,
+    pub mem: Option<u64>,
+    // Number of steps from the input's full cost down towards zero, scanned
+    // for CPU and MEM separately. A limit of zero is never run.
+    pub scan: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BudgetLimit {
+    pub cpu: u64,
+    pub mem: u64,
+}
+
+impl LimitConfig {
+    // Limits to run an input under, given what it charged without any.
+    pub fn limits(&self, full: &Measurement) -> Vec<BudgetLimit> {
+        let mut limits = Vec::new();
+
+        if self.cpu.is_some() || self.mem.is_some() {
+            limits.push(BudgetLimit {
+                cpu: self.cpu.unwrap_or(u64::MAX),
+                mem: self.mem.unwrap_or(u64::MAX),
+            });
+        }
+
+        if let Some(steps) = self.scan {
+            let fraction =
+                |full: u64, step: u64| (full as u128 * step as u128 / steps as u128) as u64;
+            for step in (1..steps).rev() {
+                let cpu = fraction(full.cpu_instruction_cost, step);
+                if cpu > 0 {
+                    limits.push(BudgetLimit { cpu, mem: u64::MAX });
+                }
+            }
+            for step in (1..steps).rev() {
+                let mem = fraction(full.memory_bytes_cost, step);
+                if mem > 0 {
+                    limits.push(BudgetLimit { cpu: u64::MAX, mem });
+                }
+            }
+        }
+
+        limits
+    }
+}
+
+// Describes what went wrong when running under `limit`, compared to the
+// unlimited run of the same input.
+pub fn problem(full: &Measurement, limited: &Measurement, limit: &BudgetLimit) -> Option<String> {
+    let crossed = full.cpu_instruction_cost > limit.cpu || full.memory_bytes_cost > limit.mem;
+
+    match limited.outcome {
+        Outcome::Panic => Some("panicked instead of returning a budget error".to_string()),
+        Outcome::BudgetExceeded if !crossed => {
+            Some("budget exceeded although the full cost fits the limit".to_string())
+        }
+        // The charge that crosses the limit is still counted, but nothing
+        // after it should be: the total can't exceed the unlimited run.
+        Outcome::BudgetExceeded
+            if limited.cpu_instruction_cost > full.cpu_instruction_cost
+                || limited.memory_bytes_cost > full.memory_bytes_cost =>
+        {
+            Some(format!(
+                "charged {} CPU / {} MEM after failing, more than the unlimited {} / {}",
+                limited.cpu_instruction_cost,
+                limited.memory_bytes_cost,
+                full.cpu_instruction_cost,
+                full.memory_bytes_cost
+            ))
+        }
+        Outcome::Ok | Outcome::Error if crossed => Some(format!(
+            "limit crossed but the call ended with `{}`",
+            limited.outcome
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise::ContextSwitches;
+    use std::time::Duration;
+
+    fn charged(cpu: u64, mem: u64, outcome: Outcome) -> Measurement {
+        Measurement {
+            seed: 0,
+            worker: 0,
+            syscall_name: "syscalls::test::dummy0".into(),
+            syscall_input: String::new(),
+            arbitrary_input: String::new(),
+            cpu_instruction_cost: cpu,
+            memory_bytes_cost: mem,
+            duration: Duration::ZERO,
+            contended: false,
+            context_switches: ContextSwitches::default(),
+            counters: None,
+            dispatch: None,
+            program_step: None,
+            outcome,
+            limit: None,
+            problem: None,
+            allocations: None,
+            size: None,
+        }
+    }
+
+    fn pairs(limits: &[BudgetLimit]) -> Vec<(u64, u64)> {
+        limits.iter().map(|limit| (limit.cpu, limit.mem)).collect()
+    }
+
+    #[test]
+    fn fixed_limits_leave_the_other_unlimited() {
+        let config = LimitConfig {
+            cpu: Some(500),
+            ..LimitConfig::default()
+        };
+        let limits = config.limits(&charged(1000, 100, Outcome::Ok));
+        assert_eq!(pairs(&limits), vec![(500, u64::MAX)]);
+    }
+
+    #[test]
+    fn scan_stops_short_of_full_and_zero() {
+        let config = LimitConfig {
+            scan: Some(4),
+            ..LimitConfig::default()
+        };
+        let limits = config.limits(&charged(1000, 100, Outcome::Ok));
+        assert_eq!(
+            pairs(&limits),
+            vec![
+                (750, u64::MAX),
+                (500, u64::MAX),
+                (250, u64::MAX),
+                (u64::MAX, 75),
+                (u64::MAX, 50),
+                (u64::MAX, 25),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_skips_limits_rounding_to_zero() {
+        let config = LimitConfig {
+            scan: Some(4),
+            ..LimitConfig::default()
+        };
+        assert!(config.limits(&charged(1, 0, Outcome::Ok)).is_empty());
+    }
+
+    #[test]
+    fn problems() {
+        let full = charged(1000, 100, Outcome::Ok);
+        let crossed = BudgetLimit {
+            cpu: 500,
+            mem: u64::MAX,
+        };
+        let fits = BudgetLimit {
+            cpu: 2000,
+            mem: u64::MAX,
+        };
+
+        let check = |limited: Measurement, limit: &BudgetLimit| problem(&full, &limited, limit);
+
+        assert_eq!(
+            check(charged(600, 50, Outcome::BudgetExceeded), &crossed),
+            None
+        );
+        assert_eq!(check(charged(1000, 100, Outcome::Ok), &fits), None);
+
+        assert_eq!(
+            check(charged(600, 50, Outcome::Panic), &crossed).as_deref(),
+            Some("panicked instead of returning a budget error")
+        );
+        assert_eq!(
+            check(charged(600, 50, Outcome::BudgetExceeded), &fits).as_deref(),
+            Some("budget exceeded although the full cost fits the limit")
+        );
+        assert_eq!(
+            check(charged(1200, 50, Outcome::BudgetExceeded), &crossed).as_deref(),
+            Some("charged 1200 CPU / 50 MEM after failing, more than the unlimited 1000 / 100")
+        );
+        assert_eq!(
+            check(charged(1000, 100, Outcome::Ok), &crossed).as_deref(),
+            Some("limit crossed but the call ended with `ok`")
+        );
+        assert_eq!(
+            check(charged(600, 50, Outcome::Error), &crossed).as_deref(),
+            Some("limit crossed but the call ended with `error`")
+        );
+    }
+}
@@ -0,0 +1,337 @@
+// In-bounds offsets and lengths for the `*_linear_memory` syscalls.
+//
+// Every call runs in a fresh instance, so memory only holds the guest's data
+// segments and zeros. Writes go past `__heap_base`, where nothing of the
+// guest lives; arrays of slices and `Val`s are read from there too, as zeros
+// they are empty symbols and `false`s. Symbols are read from the runs of
+// symbol characters in the data segments, and map keys from the guest's
+// static string arrays.
+
+use crate::fuzzcontract::{TypedFuzzInstruction, TypedModBuf, TypedModMap, TypedModVec};
+use crate::generator::Generated;
+use rand::rngs::StdRng;
+use rand::Rng;
+use soroban_sdk::{Bytes, Env, IntoVal, Map, String, Symbol, Val, Vec};
+use wasmparser::{DataKind, ExternalKind, InitExpr, Operator, Parser, Payload, TypeRef};
+
+const PAGE_SIZE: u32 = 64 * 1024;
+
+// Longest slice read or written, so lengths stay spread over small sizes
+// even when the free region is large.
+const MAX_LEN: u32 = 4096;
+
+#[derive(Clone, Debug)]
+pub struct LinearMemory {
+    // Initial size in bytes.
+    size: u32,
+    // Start of the region no data or stack lives in.
+    free: u32,
+    // Position and length of the runs of symbol characters in the data
+    // segments.
+    symbol_runs: std::vec::Vec<(u32, u32)>,
+    // Position and count of the runs of key slices in the data segments.
+    key_runs: std::vec::Vec<(u32, u32)>,
+}
+
+impl LinearMemory {
+    pub fn of(wasm: &[u8]) -> LinearMemory {
+        let mut pages = None;
+        let mut imported_globals = 0;
+        let mut globals = std::vec::Vec::new();
+        let mut heap_base_index = None;
+        // Offset and contents of the active segments.
+        let mut data = std::vec::Vec::new();
+        for payload in Parser::new(0).parse_all(wasm) {
+            match payload.unwrap() {
+                Payload::ImportSection(imports) => {
+                    for import in imports {
+                        match import.unwrap().ty {
+                            TypeRef::Memory(memory) => pages = Some(memory.initial as u32),
+                            TypeRef::Global(_) => imported_globals += 1,
+                            _ => {}
+                        }
+                    }
+                }
+                Payload::MemorySection(memories) => {
+                    if let Some(memory) = memories.into_iter().next() {
+                        pages = Some(memory.unwrap().initial as u32);
+                    }
+                }
+                Payload::GlobalSection(section) => {
+                    for global in section {
+                        globals.push(i32_const(global.unwrap().init_expr));
+                    }
+                }
+                Payload::ExportSection(exports) => {
+                    for export in exports {
+                        let export = export.unwrap();
+                        if export.name == "__heap_base" && export.kind == ExternalKind::Global {
+                            heap_base_index = Some(export.index);
+                        }
+                    }
+                }
+                Payload::DataSection(segments) => {
+                    for segment in segments {
+                        let segment = segment.unwrap();
+                        if let DataKind::Active { init_expr, .. } = segment.kind {
+                            if let Some(offset) = i32_const(init_expr) {
+                                data.push((offset as u32, segment.data.to_vec()));
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let size = pages.expect("the guest has no memory") * PAGE_SIZE;
+        let heap_base = heap_base_index
+            .and_then(|index| index.checked_sub(imported_globals))
+            .and_then(|index| globals.get(index as usize).copied().flatten());
+        // Without `__heap_base`, assume the last page is unused.
+        let free = heap_base
+            .map_or(size.saturating_sub(PAGE_SIZE), |base| base as u32)
+            .min(size);
+
+        let is_symbol_char = |c: &u8| c.is_ascii_alphanumeric() || *c == b'_';
+        let mut symbol_runs = std::vec::Vec::new();
+        for (offset, data) in &data {
+            let mut start = None;
+            for (index, c) in data.iter().chain([&0]).enumerate() {
+                match (is_symbol_char(c), start) {
+                    (true, None) => start = Some(index),
+                    (false, Some(run)) => {
+                        symbol_runs.push((offset + run as u32, (index - run) as u32));
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // A static `[&str]` is laid out as one `(pos, len)` slice per
+        // element, so where its strings are increasing symbols it is a valid
+        // array of map keys.
+        let symbol_at = |pos: u32, len: u32| {
+            data.iter().find_map(|(offset, bytes)| {
+                let start = pos.checked_sub(*offset)? as usize;
+                let symbol = bytes.get(start..start + len as usize)?;
+                (len <= 32 && symbol.iter().all(is_symbol_char)).then_some(symbol)
+            })
+        };
+        let mut key_runs = std::vec::Vec::new();
+        for (offset, bytes) in &data {
+            let key_at = |at: usize| {
+                let word = bytes.get(at..at + 8)?;
+                let pos = u32::from_le_bytes(word[..4].try_into().unwrap());
+                let len = u32::from_le_bytes(word[4..].try_into().unwrap());
+                symbol_at(pos, len)
+            };
+            // Slices are 4-aligned.
+            let mut at = (4 - offset % 4) as usize % 4;
+            while at + 8 <= bytes.len() {
+                let mut count = 0;
+                let mut last = None;
+                while let Some(key) = key_at(at + 8 * count).filter(|key| last < Some(*key)) {
+                    last = Some(key);
+                    count += 1;
+                }
+                if count >= 2 {
+                    key_runs.push((offset + at as u32, count as u32));
+                    at += 8 * count;
+                } else {
+                    at += 4;
+                }
+            }
+        }
+
+        LinearMemory {
+            size,
+            free,
+            symbol_runs,
+            key_runs,
+        }
+    }
+
+    // A slice of up to `max` bytes lying within `start..self.size`.
+    fn slice(&self, rng: &mut StdRng, start: u32, max: u32) -> (u32, u32) {
+        let len = rng.gen_range(0..=max.min(self.size - start));
+        let pos = rng.gen_range(start..=self.size - len);
+        (pos, len)
+    }
+
+    // A slice of `count` elements of `element` bytes each in the free region.
+    // When they don't fit, one starting at the end of memory, which the host
+    // rejects.
+    fn array(&self, rng: &mut StdRng, count: u32, element: u32) -> u32 {
+        match (self.size - self.free).checked_sub(count * element) {
+            Some(spare) => self.free + rng.gen_range(0..=spare),
+            None => self.size,
+        }
+    }
+
+    // The most elements of `element` bytes fitting the free region, capped
+    // like slice lengths.
+    fn max_count(&self, element: u32) -> u32 {
+        (self.size - self.free).min(MAX_LEN) / element
+    }
+}
+
+pub fn generate(env: &Env, rng: &mut StdRng, memory: &LinearMemory) -> Generated {
+    let bytes = |rng: &mut StdRng| {
+        let mut data = vec![0u8; rng.gen_range(0..=MAX_LEN) as usize];
+        rng.fill(&mut data[..]);
+        data
+    };
+
+    let (instruction, len) = match rng.gen_range(0..12) {
+        0 => {
+            let (pos, len) = memory.slice(rng, 0, MAX_LEN);
+            let instruction = TypedModBuf::BytesNewFromLinearMemory(pos, len);
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        1 => {
+            let (pos, len) = memory.slice(rng, 0, MAX_LEN);
+            let instruction = TypedModBuf::StringNewFromLinearMemory(pos, len);
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        2 => {
+            let (pos, len) = if memory.symbol_runs.is_empty() {
+                (memory.free, 0)
+            } else {
+                let (run, run_len) = memory.symbol_runs[rng.gen_range(0..memory.symbol_runs.len())];
+                let len = rng.gen_range(0..=run_len.min(32));
+                (run + rng.gen_range(0..=run_len - len), len)
+            };
+            let instruction = TypedModBuf::SymbolNewFromLinearMemory(pos, len);
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        3 => {
+            let data = bytes(rng);
+            let b_pos = rng.gen_range(0..=data.len() as u32);
+            let (lm_pos, len) = memory.slice(rng, 0, data.len() as u32 - b_pos);
+            let instruction = TypedModBuf::BytesCopyFromLinearMemory(
+                Bytes::from_slice(env, &data),
+                b_pos,
+                lm_pos,
+                len,
+            );
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        4 => {
+            let data = bytes(rng);
+            let b_pos = rng.gen_range(0..=data.len() as u32);
+            let (lm_pos, len) = memory.slice(rng, memory.free, data.len() as u32 - b_pos);
+            let instruction = TypedModBuf::BytesCopyToLinearMemory(
+                Bytes::from_slice(env, &data),
+                b_pos,
+                lm_pos,
+                len,
+            );
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        5 => {
+            let text_len = rng.gen_range(0..=MAX_LEN) as usize;
+            let text = symbol_chars(rng, text_len);
+            let s_pos = rng.gen_range(0..=text.len() as u32);
+            let (lm_pos, len) = memory.slice(rng, memory.free, text.len() as u32 - s_pos);
+            let instruction = TypedModBuf::StringCopyToLinearMemory(
+                String::from_slice(env, &text),
+                s_pos,
+                lm_pos,
+                len,
+            );
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        6 => {
+            // Longer than 9 characters, so the symbol is an object.
+            let symbol_len = rng.gen_range(10..=32);
+            let symbol = symbol_chars(rng, symbol_len);
+            let s_pos = rng.gen_range(0..=symbol.len() as u32);
+            let (lm_pos, len) = memory.slice(rng, memory.free, symbol.len() as u32 - s_pos);
+            let instruction = TypedModBuf::SymbolCopyToLinearMemory(
+                Symbol::new(env, &symbol),
+                s_pos,
+                lm_pos,
+                len,
+            );
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        // Scans every slice: the symbol is never empty, the slices all are.
+        7 => {
+            let len = rng.gen_range(0..=memory.max_count(8));
+            let pos = memory.array(rng, len, 8);
+            let symbol_len = rng.gen_range(1..=32);
+            let symbol = symbol_chars(rng, symbol_len);
+            let instruction =
+                TypedModBuf::SymbolIndexInLinearMemory(Symbol::new(env, &symbol), pos, len);
+            (TypedFuzzInstruction::Buf(instruction), len)
+        }
+        8 => {
+            let len = rng.gen_range(0..=memory.max_count(8));
+            let pos = memory.array(rng, len, 8);
+            let instruction = TypedModVec::VecNewFromLinearMemory(pos, len);
+            (TypedFuzzInstruction::Vec(instruction), len)
+        }
+        9 => {
+            let len = rng.gen_range(0..=memory.max_count(8));
+            let pos = memory.array(rng, len, 8);
+            let mut vec = Vec::new(env);
+            for _ in 0..len {
+                vec.push_back(rng.gen::<u32>().into_val(env));
+            }
+            let instruction = TypedModVec::VecUnpackToLinearMemory(vec, pos, len);
+            (TypedFuzzInstruction::Vec(instruction), len)
+        }
+        // Zeroed key slices are all the empty symbol, and keys must be
+        // unique, so without key runs these maps have at most one entry.
+        10 => {
+            let (keys_pos, len) = if memory.key_runs.is_empty() {
+                let len = rng.gen_range(0..=1);
+                (memory.array(rng, len, 8), len)
+            } else {
+                let (run, run_len) = memory.key_runs[rng.gen_range(0..memory.key_runs.len())];
+                let len = rng.gen_range(0..=run_len.min(memory.max_count(8)));
+                (run + 8 * rng.gen_range(0..=run_len - len), len)
+            };
+            let vals_pos = memory.array(rng, len, 8);
+            let instruction = TypedModMap::MapNewFromLinearMemory(keys_pos, vals_pos, len);
+            (TypedFuzzInstruction::Map(instruction), len)
+        }
+        _ => {
+            let mut map: Map<Val, Val> = Map::new(env);
+            map.set(
+                Symbol::new(env, "").into_val(env),
+                rng.gen::<u32>().into_val(env),
+            );
+            let keys_pos = memory.array(rng, 1, 8);
+            let vals_pos = memory.array(rng, 1, 8);
+            let instruction = TypedModMap::MapUnpackToLinearMemory(map, keys_pos, vals_pos, 1);
+            (TypedFuzzInstruction::Map(instruction), 1)
+        }
+    };
+
+    Generated {
+        instruction,
+        description: format!(
+            "len={} memory={} free_from={}",
+            len, memory.size, memory.free
+        ),
+        size: Some(len as u64),
+    }
+}
+
+fn symbol_chars(rng: &mut StdRng, len: usize) -> std::string::String {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_";
+    (0..len)
+        .map(|_| CHARS[rng.gen_range(0..CHARS.len())] as char)
+        .collect()
+}
+
+// Only `i32.const` initializers are evaluated.
+fn i32_const(expr: InitExpr) -> Option<i32> {
+    match expr.get_operators_reader().read().unwrap() {
+        Operator::I32Const { value } => Some(value),
+        _ => None,
+    }
+}
@@ -0,0 +1,158 @@
+use crate::alloc::AllocStats;
+use crate::limits::BudgetLimit;
+use crate::noise::ContextSwitches;
+use crate::perf::CounterValues;
+use soroban_sdk::xdr::ScErrorType;
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+// Median cost of invoking the guest with the no-op `Test` instruction:
+// argument conversion, VM instantiation, guest decoding and the return path,
+// plus a single trivial `dummy0` call.
+#[derive(Clone, Copy, Debug)]
+pub struct DispatchBaseline {
+    pub cpu_instruction_cost: u64,
+    pub memory_bytes_cost: u64,
+    pub duration: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Ok,
+    Error,
+    BudgetExceeded,
+    Panic,
+}
+
+impl Outcome {
+    pub fn of<T, E>(result: &Result<T, Result<soroban_sdk::Error, E>>) -> Outcome {
+        match result {
+            Ok(_) => Outcome::Ok,
+            Err(Ok(error)) if error.is_type(ScErrorType::Budget) => Outcome::BudgetExceeded,
+            Err(_) => Outcome::Error,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Ok => "ok",
+            Outcome::Error => "error",
+            Outcome::BudgetExceeded => "budget-exceeded",
+            Outcome::Panic => "panic",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    pub seed: u64,
+    pub worker: u64,
+    // Owned only for the functions of a contract given with `--wasm`.
+    pub syscall_name: Cow<'static, str>,
+    pub syscall_input: String,
+    pub arbitrary_input: String,
+    pub cpu_instruction_cost: u64,
+    pub memory_bytes_cost: u64,
+    pub duration: Duration,
+    // Set when other workers were running on shared cores, so the duration
+    // can't be compared against single-threaded measurements.
+    pub contended: bool,
+    pub context_switches: ContextSwitches,
+    // Only present when running with `--perf`.
+    pub counters: Option<CounterValues>,
+    // Only present when running with `--isolate-host`.
+    pub dispatch: Option<DispatchBaseline>,
+    // Index of the step within its program when running with `--program-len`.
+    pub program_step: Option<u64>,
+    // Panics are only recorded under limits and with `--keep-panics`.
+    pub outcome: Outcome,
+    // Only present for the limited runs of `--cpu-limit`, `--mem-limit` and
+    // `--limit-scan`.
+    pub limit: Option<BudgetLimit>,
+    // How the run under `limit` misbehaved, if it did.
+    pub problem: Option<String>,
+    // Only present when running with `--track-alloc`.
+    pub allocations: Option<AllocStats>,
+    // Input size with `--sweep` and the generators that build inputs
+    // directly.
+    pub size: Option<u64>,
+}
+
+impl Measurement {
+    pub const CSV_HEADER: &'static str = concat!(
+        "Syscalls|SyscallsInput|ArbitraryInput|CPU|MEM|Duration",
+        "|Seed|Worker|Contended|VoluntaryCtxSwitches|InvoluntaryCtxSwitches",
+        "|Instructions|Cycles",
+        "|DispatchCPU|DispatchMEM|DispatchDuration|HostDuration",
+        "|SyscallCPU|SyscallMEM|ProgramStep",
+        "|Outcome|CPULimit|MEMLimit",
+        "|AllocBytes|AllocPeak|RssPeakDelta|Size",
+    );
+
+    pub fn to_csv(&self) -> String {
+        let counters = self.counters.as_ref();
+        let dispatch = self.dispatch.as_ref();
+        let allocations = self.allocations.as_ref();
+
+        format!(
+            "{:?}|\"{}\"|\"{}\"|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            self.syscall_name,
+            self.syscall_input,
+            self.arbitrary_input,
+            self.cpu_instruction_cost,
+            self.memory_bytes_cost,
+            self.duration.as_nanos(),
+            self.seed,
+            self.worker,
+            self.contended,
+            self.context_switches.voluntary,
+            self.context_switches.involuntary,
+            optional(counters.map(|c| c.instructions)),
+            optional(counters.map(|c| c.cycles)),
+            optional(dispatch.map(|d| d.cpu_instruction_cost)),
+            optional(dispatch.map(|d| d.memory_bytes_cost)),
+            optional(dispatch.map(|d| d.duration.as_nanos())),
+            optional(self.host_duration().map(|d| d.as_nanos())),
+            optional(self.syscall_cpu()),
+            optional(self.syscall_mem()),
+            optional(self.program_step),
+            self.outcome,
+            optional(self.limit.map(|l| l.cpu)),
+            optional(self.limit.map(|l| l.mem)),
+            optional(allocations.map(|a| a.bytes)),
+            optional(allocations.map(|a| a.peak)),
+            optional(allocations.and_then(|a| a.rss_peak)),
+            optional(self.size),
+        )
+    }
+
+    // Wall time of the call minus the worker's median dispatch time, clamped
+    // at zero. An estimate of the time spent past the dispatch, not a timing
+    // of the host function itself.
+    pub fn host_duration(&self) -> Option<Duration> {
+        self.dispatch
+            .map(|dispatch| self.duration.saturating_sub(dispatch.duration))
+    }
+
+    // Charges minus the per-invocation setup measured by the dispatch baseline.
+    pub fn syscall_cpu(&self) -> Option<u64> {
+        self.dispatch.map(|dispatch| {
+            self.cpu_instruction_cost
+                .saturating_sub(dispatch.cpu_instruction_cost)
+        })
+    }
+
+    pub fn syscall_mem(&self) -> Option<u64> {
+        self.dispatch.map(|dispatch| {
+            self.memory_bytes_cost
+                .saturating_sub(dispatch.memory_bytes_cost)
+        })
+    }
+}
+
+fn optional<T: Display>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
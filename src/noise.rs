@@ -0,0 +1,114 @@
+// Controls for scheduling and frequency noise in wall-clock measurements.
+//
+// Everything here is best effort: failures (e.g. missing CAP_SYS_NICE) are
+// reported and the run continues unpinned or at normal priority.
+
+use std::fs;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ContextSwitches {
+    pub voluntary: u64,
+    pub involuntary: u64,
+}
+
+impl ContextSwitches {
+    pub fn since(&self, earlier: &ContextSwitches) -> ContextSwitches {
+        ContextSwitches {
+            voluntary: self.voluntary.saturating_sub(earlier.voluntary),
+            involuntary: self.involuntary.saturating_sub(earlier.involuntary),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(core: usize) -> bool {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_core: usize) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+pub fn raise_priority() -> bool {
+    unsafe {
+        let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+        libc::setpriority(libc::PRIO_PROCESS, tid, -20) == 0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn raise_priority() -> bool {
+    false
+}
+
+// Counts for the calling thread only, so other workers don't show up as noise.
+#[cfg(target_os = "linux")]
+pub fn context_switches() -> ContextSwitches {
+    unsafe {
+        let mut usage: libc::rusage = std::mem::zeroed();
+        if libc::getrusage(libc::RUSAGE_THREAD, &mut usage) != 0 {
+            return ContextSwitches::default();
+        }
+        ContextSwitches {
+            voluntary: usage.ru_nvcsw as u64,
+            involuntary: usage.ru_nivcsw as u64,
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn context_switches() -> ContextSwitches {
+    ContextSwitches::default()
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+pub fn report_frequency_state(cores: &[usize]) {
+    let cores: Vec<usize> = if cores.is_empty() {
+        vec![0]
+    } else {
+        cores.to_vec()
+    };
+
+    for core in cores {
+        let base = format!("/sys/devices/system/cpu/cpu{}/cpufreq", core);
+        match read_trimmed(&format!("{}/scaling_governor", base)) {
+            Some(governor) => {
+                println!("cpu{}: governor {}", core, governor);
+                if governor != "performance" {
+                    println!(
+                        "warning: cpu{} is not using the performance governor; durations may drift",
+                        core
+                    );
+                }
+            }
+            None => println!("cpu{}: governor unknown", core),
+        }
+    }
+
+    // intel_pstate reports "no_turbo", acpi-cpufreq and amd-pstate report "boost".
+    if let Some(no_turbo) = read_trimmed("/sys/devices/system/cpu/intel_pstate/no_turbo") {
+        let enabled = no_turbo == "0";
+        println!("turbo: {}", if enabled { "enabled" } else { "disabled" });
+        if enabled {
+            println!("warning: turbo is enabled; durations depend on thermal headroom");
+        }
+    } else if let Some(boost) = read_trimmed("/sys/devices/system/cpu/cpufreq/boost") {
+        let enabled = boost == "1";
+        println!("boost: {}", if enabled { "enabled" } else { "disabled" });
+        if enabled {
+            println!("warning: boost is enabled; durations depend on thermal headroom");
+        }
+    } else {
+        println!("turbo: unknown");
+    }
+}
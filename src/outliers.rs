@@ -0,0 +1,83 @@
+// Re-runs the inputs whose charged CPU per nanosecond is among the highest
+// or lowest `--explain-outliers` percent of their syscall, with diagnostic
+// events on, and writes what the host recorded to `<outliers dir>/<seed>.txt`.
+// Points that stand out on the boxplot can then be looked into without
+// reproducing them by hand.
+
+use crate::config::Config;
+use crate::measurement::Measurement;
+use crate::results;
+use crate::worker::Worker;
+use std::fs;
+use std::path::Path;
+
+// What the host recorded while re-running one input.
+pub struct Explanation {
+    pub measurement: Measurement,
+    // Per cost type, as printed by the budget.
+    pub budget: String,
+    pub events: String,
+    // Contract logs (`log!`) from `env.logs()`.
+    pub logs: Vec<String>,
+}
+
+fn cpu_per_ns(measurement: &Measurement) -> f64 {
+    measurement.cpu_instruction_cost as f64 / measurement.duration.as_nanos().max(1) as f64
+}
+
+pub fn explain(config: &Config, percent: f64, measurements: &[Measurement]) {
+    let mut outliers: Vec<(&Measurement, &str)> = Vec::new();
+    for (_, mut measurements) in results::by_syscall(measurements, |m| m.syscall_name.as_ref()) {
+        let count = (measurements.len() as f64 * percent / 100.0) as usize;
+        if count == 0 {
+            continue;
+        }
+        measurements.sort_by(|a, b| cpu_per_ns(a).partial_cmp(&cpu_per_ns(b)).unwrap());
+        outliers.extend(measurements[..count].iter().map(|m| (*m, "low")));
+        outliers.extend(
+            measurements[measurements.len() - count..]
+                .iter()
+                .map(|m| (*m, "high")),
+        );
+    }
+    if outliers.is_empty() {
+        println!("no syscall has enough rows for outliers at {}%", percent);
+        return;
+    }
+
+    let dir = Path::new(&config.outliers_dir);
+    fs::create_dir_all(dir).unwrap();
+
+    println!(
+        "explaining {} outliers into {}",
+        outliers.len(),
+        dir.display()
+    );
+    for (row, side) in outliers {
+        // A fresh worker each time, so events and logs are only this input's.
+        let worker = Worker::new(config, 0);
+        let explanation = match worker.explain(row.seed) {
+            Some(explanation) => explanation,
+            None => {
+                println!("warning: seed {} didn't run again", row.seed);
+                continue;
+            }
+        };
+
+        let contents = format!(
+            "{} CPU/ns for {} ({:.3})\n\n{}\n{}\n\n\
+             == rerun, with diagnostics ==\n{}\n\n\
+             == budget ==\n{}\n\n== events ==\n{}\n\n== logs ==\n{}\n",
+            side,
+            row.syscall_name,
+            cpu_per_ns(row),
+            Measurement::CSV_HEADER,
+            row.to_csv(),
+            explanation.measurement.to_csv(),
+            explanation.budget,
+            explanation.events,
+            explanation.logs.join("\n")
+        );
+        fs::write(dir.join(format!("{}.txt", row.seed)), contents).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+// Retired instructions and cycles from the kernel's hardware counters, so the
+// CPU budget can be compared with what the host actually executed.
+//
+// Counters follow the opening thread and exclude kernel time, which keeps them
+// usable with the default `perf_event_paranoid` setting.
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CounterValues {
+    pub instructions: u64,
+    pub cycles: u64,
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    pub const PERF_TYPE_HARDWARE: u32 = 0;
+    pub const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    pub const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+
+    pub const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+    pub const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+    pub const PERF_EVENT_IOC_RESET: libc::c_ulong = 0x2403;
+
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    // `PERF_ATTR_SIZE_VER0` layout; the kernel zero-extends older sizes.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        type_: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    pub struct Counter {
+        fd: libc::c_int,
+    }
+
+    impl Counter {
+        pub fn open(config: u64) -> Option<Counter> {
+            let attr = PerfEventAttr {
+                type_: PERF_TYPE_HARDWARE,
+                size: std::mem::size_of::<PerfEventAttr>() as u32,
+                config,
+                flags: FLAG_DISABLED | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                ..Default::default()
+            };
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const PerfEventAttr,
+                    0,
+                    -1,
+                    -1,
+                    0,
+                )
+            };
+            if fd < 0 {
+                None
+            } else {
+                Some(Counter {
+                    fd: fd as libc::c_int,
+                })
+            }
+        }
+
+        pub fn ioctl(&self, request: libc::c_ulong) {
+            unsafe {
+                libc::ioctl(self.fd, request, 0);
+            }
+        }
+
+        pub fn read(&self) -> u64 {
+            let mut value = 0u64;
+            let n = unsafe {
+                libc::read(
+                    self.fd,
+                    &mut value as *mut u64 as *mut libc::c_void,
+                    std::mem::size_of::<u64>(),
+                )
+            };
+            if n as usize == std::mem::size_of::<u64>() {
+                value
+            } else {
+                0
+            }
+        }
+    }
+
+    impl Drop for Counter {
+        fn drop(&mut self) {
+            unsafe {
+                libc::close(self.fd);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub struct HardwareCounters {
+    instructions: sys::Counter,
+    cycles: sys::Counter,
+}
+
+#[cfg(target_os = "linux")]
+impl HardwareCounters {
+    // Must be called on the thread that will be measured.
+    pub fn open() -> Option<HardwareCounters> {
+        Some(HardwareCounters {
+            instructions: sys::Counter::open(sys::PERF_COUNT_HW_INSTRUCTIONS)?,
+            cycles: sys::Counter::open(sys::PERF_COUNT_HW_CPU_CYCLES)?,
+        })
+    }
+
+    pub fn start(&self) {
+        for counter in [&self.instructions, &self.cycles] {
+            counter.ioctl(sys::PERF_EVENT_IOC_RESET);
+            counter.ioctl(sys::PERF_EVENT_IOC_ENABLE);
+        }
+    }
+
+    pub fn stop(&self) -> CounterValues {
+        for counter in [&self.instructions, &self.cycles] {
+            counter.ioctl(sys::PERF_EVENT_IOC_DISABLE);
+        }
+        CounterValues {
+            instructions: self.instructions.read(),
+            cycles: self.cycles.read(),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub struct HardwareCounters;
+
+#[cfg(not(target_os = "linux"))]
+impl HardwareCounters {
+    pub fn open() -> Option<HardwareCounters> {
+        None
+    }
+
+    pub fn start(&self) {}
+
+    pub fn stop(&self) -> CounterValues {
+        CounterValues::default()
+    }
+}
@@ -0,0 +1,147 @@
+// Programs of dependent `Vec`/`Map` instructions. Every step is a separate
+// invocation of the guest on objects rebuilt from scratch: the collections a
+// step receives hold what the earlier steps produced, but no host object
+// carries over from one step to the next.
+
+use crate::syscalls::{TypedFuzzInstructionPrototype, TypedModMapPrototype, TypedModVecPrototype};
+use soroban_sdk::arbitrary::arbitrary::{self, Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::{Map, Val, Vec};
+
+type ValPrototype = <Val as SorobanArbitrary>::Prototype;
+type VecPrototype = <Vec<Val> as SorobanArbitrary>::Prototype;
+type MapPrototype = <Map<Val, Val> as SorobanArbitrary>::Prototype;
+
+// One step of a program. Steps operate on the `Vec` and `Map` built up by the
+// earlier steps instead of carrying their own random collection.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum ProgramStep {
+    VecPushBack(ValPrototype),
+    VecPushFront(ValPrototype),
+    VecInsert(u32, ValPrototype),
+    VecPut(u32, ValPrototype),
+    VecPopBack,
+    VecGet(u32),
+    VecLen,
+    VecBinarySearch(ValPrototype),
+    VecFirstIndexOf(ValPrototype),
+    VecLastIndexOf(ValPrototype),
+    MapPut(ValPrototype, ValPrototype),
+    MapGet(ValPrototype),
+    MapHas(ValPrototype),
+    MapLen,
+    MapKeys,
+    MapValues,
+    MapMinKey,
+    MapMaxKey,
+}
+
+#[derive(Clone, Debug)]
+pub struct Program {
+    pub steps: std::vec::Vec<ProgramStep>,
+}
+
+impl Program {
+    pub fn arbitrary(u: &mut Unstructured, max_len: usize) -> arbitrary::Result<Program> {
+        let mut steps = std::vec::Vec::new();
+        while steps.len() < max_len && !u.is_empty() {
+            steps.push(ProgramStep::arbitrary(u)?);
+        }
+        Ok(Program { steps })
+    }
+
+    // Replays the program, applying the effect of each step to the collections
+    // so later instructions see everything earlier ones produced. Indexes are
+    // wrapped into range so steps exercise the success paths.
+    pub fn instructions(&self) -> std::vec::Vec<TypedFuzzInstructionPrototype> {
+        use TypedFuzzInstructionPrototype as I;
+
+        let mut vec: VecPrototype = std::vec::Vec::new();
+        let mut map: MapPrototype = std::vec::Vec::new();
+
+        let wrap = |index: u32, len: usize| {
+            if len == 0 {
+                0
+            } else {
+                index % len as u32
+            }
+        };
+
+        self.steps
+            .iter()
+            .map(|step| match step {
+                ProgramStep::VecPushBack(v) => {
+                    let instruction =
+                        I::Vec(TypedModVecPrototype::VecPushBack(vec.clone(), v.clone()));
+                    vec.push(v.clone());
+                    instruction
+                }
+                ProgramStep::VecPushFront(v) => {
+                    let instruction =
+                        I::Vec(TypedModVecPrototype::VecPushFront(vec.clone(), v.clone()));
+                    vec.insert(0, v.clone());
+                    instruction
+                }
+                ProgramStep::VecInsert(i, v) => {
+                    let i = wrap(*i, vec.len() + 1);
+                    let instruction =
+                        I::Vec(TypedModVecPrototype::VecInsert(vec.clone(), i, v.clone()));
+                    vec.insert(i as usize, v.clone());
+                    instruction
+                }
+                ProgramStep::VecPut(i, v) => {
+                    let i = wrap(*i, vec.len());
+                    let instruction =
+                        I::Vec(TypedModVecPrototype::VecPut(vec.clone(), i, v.clone()));
+                    if let Some(slot) = vec.get_mut(i as usize) {
+                        *slot = v.clone();
+                    }
+                    instruction
+                }
+                ProgramStep::VecPopBack => {
+                    let instruction = I::Vec(TypedModVecPrototype::VecPopBack(vec.clone()));
+                    vec.pop();
+                    instruction
+                }
+                ProgramStep::VecGet(i) => {
+                    let i = wrap(*i, vec.len());
+                    I::Vec(TypedModVecPrototype::VecGet(vec.clone(), i))
+                }
+                ProgramStep::VecLen => I::Vec(TypedModVecPrototype::VecLen(vec.clone())),
+                ProgramStep::VecBinarySearch(v) => I::Vec(TypedModVecPrototype::VecBinarySearch(
+                    vec.clone(),
+                    v.clone(),
+                )),
+                ProgramStep::VecFirstIndexOf(v) => I::Vec(TypedModVecPrototype::VecFirstIndexOf(
+                    vec.clone(),
+                    v.clone(),
+                )),
+                ProgramStep::VecLastIndexOf(v) => {
+                    I::Vec(TypedModVecPrototype::VecLastIndexOf(vec.clone(), v.clone()))
+                }
+                // Duplicate keys collapse when the prototype is converted, so
+                // appending is enough to model an update.
+                ProgramStep::MapPut(k, v) => {
+                    let instruction = I::Map(TypedModMapPrototype::MapPut(
+                        map.clone(),
+                        k.clone(),
+                        v.clone(),
+                    ));
+                    map.push((k.clone(), v.clone()));
+                    instruction
+                }
+                ProgramStep::MapGet(k) => {
+                    I::Map(TypedModMapPrototype::MapGet(map.clone(), k.clone()))
+                }
+                ProgramStep::MapHas(k) => {
+                    I::Map(TypedModMapPrototype::MapHas(map.clone(), k.clone()))
+                }
+                ProgramStep::MapLen => I::Map(TypedModMapPrototype::MapLen(map.clone())),
+                ProgramStep::MapKeys => I::Map(TypedModMapPrototype::MapKeys(map.clone())),
+                ProgramStep::MapValues => I::Map(TypedModMapPrototype::MapValues(map.clone())),
+                ProgramStep::MapMinKey => I::Map(TypedModMapPrototype::MapMinKey(map.clone())),
+                ProgramStep::MapMaxKey => I::Map(TypedModMapPrototype::MapMaxKey(map.clone())),
+            })
+            .collect()
+    }
+}
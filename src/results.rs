@@ -0,0 +1,109 @@
+// Reads back the CSV written by a run, for the subcommands that compare runs.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+#[derive(Clone, Debug)]
+pub struct Row {
+    pub syscall_name: String,
+    // Both input columns together; they are `Debug` output and can contain
+    // the separator themselves.
+    pub input: String,
+    pub cpu_instruction_cost: u64,
+    pub memory_bytes_cost: u64,
+    // Nanoseconds.
+    pub duration: u64,
+    pub seed: u64,
+    pub program_step: Option<u64>,
+    // `None` for rows written before the column existed.
+    pub outcome: Option<String>,
+    pub size: Option<u64>,
+    // CPU and MEM limit of the rows run under one.
+    pub limit: Option<(u64, u64)>,
+}
+
+impl Row {
+    // Identifies the same input across runs started with the same `--seed`.
+    // A seed has several rows in program, sweep and limit runs.
+    pub fn key(&self) -> (u64, Option<u64>, Option<u64>, Option<(u64, u64)>) {
+        (self.seed, self.program_step, self.size, self.limit)
+    }
+}
+
+// Runs append to the output file, so a header can appear more than once; each
+// one applies to the rows that follow it.
+pub fn read(path: &str) -> Vec<Row> {
+    let contents = fs::read_to_string(path).unwrap();
+
+    let mut columns: Vec<&str> = Vec::new();
+    let mut rows = Vec::new();
+    for line in contents.lines().filter(|line| !line.is_empty()) {
+        if line.starts_with("Syscalls|") {
+            columns = line.split('|').collect();
+            continue;
+        }
+        assert!(!columns.is_empty(), "{} has no header", path);
+
+        // The name comes first and every column after the two inputs is
+        // plain, so split from both ends.
+        let (name, rest) = line.split_once('|').unwrap();
+        let mut trailing: Vec<&str> = rest.rsplitn(columns.len() - 2, '|').collect();
+        let input = trailing.pop().unwrap();
+        trailing.reverse();
+
+        let field = |column: &str| {
+            let index = columns.iter().position(|c| *c == column);
+            index.map(|index| trailing[index - 3])
+        };
+        let number = |column: &str| field(column).unwrap().parse::<u64>().unwrap();
+        let optional = |column: &str| {
+            field(column)
+                .filter(|value| !value.is_empty())
+                .map(|value| value.parse::<u64>().unwrap())
+        };
+
+        rows.push(Row {
+            syscall_name: name.trim_matches('"').to_string(),
+            input: input.to_string(),
+            cpu_instruction_cost: number("CPU"),
+            memory_bytes_cost: number("MEM"),
+            duration: number("Duration"),
+            seed: number("Seed"),
+            program_step: optional("ProgramStep"),
+            outcome: field("Outcome").map(str::to_string),
+            size: optional("Size"),
+            limit: optional("CPULimit").zip(optional("MEMLimit")),
+        });
+    }
+    rows
+}
+
+pub fn by_syscall<T>(
+    items: impl IntoIterator<Item = T>,
+    name: impl Fn(&T) -> &str,
+) -> BTreeMap<String, Vec<T>> {
+    let mut groups: BTreeMap<String, Vec<T>> = BTreeMap::new();
+    for item in items {
+        groups
+            .entry(name(&item).to_string())
+            .or_default()
+            .push(item);
+    }
+    groups
+}
+
+pub fn median<T: Copy + Default + PartialOrd>(values: impl IntoIterator<Item = T>) -> T {
+    let mut values: Vec<T> = values.into_iter().collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values.get(values.len() / 2).copied().unwrap_or_default()
+}
+
+// Formats `before -> after` with the relative change.
+pub fn change(before: u64, after: u64) -> String {
+    if before == 0 {
+        format!("{} -> {}", before, after)
+    } else {
+        let percent = (after as f64 - before as f64) * 100.0 / before as f64;
+        format!("{} -> {} ({:+.1}%)", before, after, percent)
+    }
+}
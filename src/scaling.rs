@@ -0,0 +1,77 @@
+// `scaling <results.csv>...`: fits charged CPU and wall time linearly against
+// the `Size` column per syscall, for runs whose inputs record a size
+// (`--sweep` and the generators building inputs directly). A charge that
+// tracks size much better or worse than the wall time does shows up as a
+// gap between the two R² columns. Charges and wall time of these rows both
+// cover only the call, so CPUPerNs divides slopes of the same window.
+
+use crate::results::{self, Row};
+
+struct Fit {
+    slope: f64,
+    r2: f64,
+}
+
+impl Fit {
+    fn of(points: &[(f64, f64)]) -> Fit {
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+        let mut sxx = 0.0;
+        let mut sxy = 0.0;
+        let mut syy = 0.0;
+        for (x, y) in points {
+            sxx += (x - mean_x) * (x - mean_x);
+            sxy += (x - mean_x) * (y - mean_y);
+            syy += (y - mean_y) * (y - mean_y);
+        }
+
+        Fit {
+            slope: if sxx == 0.0 { 0.0 } else { sxy / sxx },
+            r2: if sxx == 0.0 || syy == 0.0 {
+                0.0
+            } else {
+                sxy * sxy / (sxx * syy)
+            },
+        }
+    }
+}
+
+pub fn run(args: impl Iterator<Item = String>) {
+    let paths: Vec<String> = args.collect();
+    assert!(!paths.is_empty(), "usage: scaling <results.csv>...");
+
+    let rows: Vec<Row> = paths
+        .iter()
+        .flat_map(|path| results::read(path))
+        .filter(|row| row.size.is_some())
+        .collect();
+    assert!(!rows.is_empty(), "no rows with a size");
+
+    println!("Syscalls|Rows|CPUPerUnit|NsPerUnit|CPUPerNs|CPUR2|NsR2");
+    for (name, rows) in results::by_syscall(rows, |row| &row.syscall_name) {
+        let point = |y: fn(&Row) -> u64| -> Vec<(f64, f64)> {
+            rows.iter()
+                .map(|row| (row.size.unwrap() as f64, y(row) as f64))
+                .collect()
+        };
+        let cpu = Fit::of(&point(|row| row.cpu_instruction_cost));
+        let ns = Fit::of(&point(|row| row.duration));
+
+        println!(
+            "{}|{}|{:.1}|{:.2}|{:.2}|{:.3}|{:.3}",
+            name,
+            rows.len(),
+            cpu.slope,
+            ns.slope,
+            if ns.slope == 0.0 {
+                0.0
+            } else {
+                cpu.slope / ns.slope
+            },
+            cpu.r2,
+            ns.r2
+        );
+    }
+}
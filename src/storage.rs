@@ -0,0 +1,106 @@
+// Seeds the fuzz contract's storage and biases ledger keys toward its entries.
+
+use crate::fuzzcontract::{self, FakeVal, FuzzInstruction, TypedFuzzInstruction, TypedModLedger};
+use rand::Rng;
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::{Address, Bytes, Env, IntoVal, Val};
+
+#[derive(Clone, Debug)]
+pub struct StorageConfig {
+    pub entries: u32,
+    pub entry_size: u32,
+    // Probability that a generated ledger key is replaced by a seeded one.
+    pub hit_rate: f64,
+}
+
+pub struct SeededStorage {
+    config: StorageConfig,
+    contract_id: Address,
+}
+
+impl SeededStorage {
+    pub fn new(env: &Env, contract_id: &Address, config: &StorageConfig) -> SeededStorage {
+        let storage = SeededStorage {
+            config: config.clone(),
+            contract_id: contract_id.clone(),
+        };
+        for index in 0..config.entries {
+            storage.restore(env, index);
+        }
+        assert!(
+            config.entries == 0 || storage.hits(env),
+            "the guest doesn't find the seeded entries; they are in persistent storage"
+        );
+        storage
+    }
+
+    // The guest picks the storage type of its ledger instructions itself, so
+    // check that it reads a seeded key as present. A `Get` of a missing key
+    // fails.
+    fn hits(&self, env: &Env) -> bool {
+        let client = fuzzcontract::Client::new(env, &self.contract_id);
+        let key = FakeVal(Self::key(env, 0).get_payload());
+        let get = FuzzInstruction::Typed(TypedFuzzInstruction::Ledger(
+            TypedModLedger::GetContractData(key),
+        ));
+
+        env.budget().reset_unlimited();
+        fuzz_catch_panic(|| client.try_run(&get).is_ok()).unwrap_or(false)
+    }
+
+    fn key(env: &Env, index: u32) -> Val {
+        index.into_val(env)
+    }
+
+    fn value(&self, env: &Env, index: u32) -> Bytes {
+        let mut value = Bytes::new(env);
+        for i in 0..self.config.entry_size {
+            value.push_back(index.wrapping_add(i) as u8);
+        }
+        value
+    }
+
+    // Puts the entry at `index` back to its seeded value.
+    pub fn restore(&self, env: &Env, index: u32) {
+        let key = Self::key(env, index);
+        let value = self.value(env, index);
+        env.as_contract(&self.contract_id, || {
+            env.storage().persistent().set(&key, &value);
+        });
+    }
+
+    // Replaces the key of a ledger instruction with a seeded one at the
+    // configured rate. Returns the index of the chosen entry, so it can be
+    // restored if the instruction deletes or overwrites it.
+    pub fn bias(
+        &self,
+        env: &Env,
+        rng: &mut impl Rng,
+        instruction: TypedFuzzInstruction,
+    ) -> (TypedFuzzInstruction, Option<u32>) {
+        if self.config.entries == 0 || !rng.gen_bool(self.config.hit_rate) {
+            return (instruction, None);
+        }
+
+        let index = rng.gen_range(0..self.config.entries);
+        let key = FakeVal(Self::key(env, index).get_payload());
+
+        let instruction = match instruction {
+            TypedFuzzInstruction::Ledger(v) => TypedFuzzInstruction::Ledger(match v {
+                TypedModLedger::BumpContractData(_, v_1) => {
+                    TypedModLedger::BumpContractData(key, v_1)
+                }
+                TypedModLedger::DelContractData(_) => TypedModLedger::DelContractData(key),
+                TypedModLedger::GetContractData(_) => TypedModLedger::GetContractData(key),
+                TypedModLedger::HasContractData(_) => TypedModLedger::HasContractData(key),
+                TypedModLedger::PutContractData(_, v_1, v_2) => {
+                    TypedModLedger::PutContractData(key, v_1, v_2)
+                }
+                v => return (TypedFuzzInstruction::Ledger(v), None),
+            }),
+            v => return (v, None),
+        };
+
+        (instruction, Some(index))
+    }
+}
@@ -0,0 +1,159 @@
+// Inputs of one syscall built at each size of a geometric range.
+
+use crate::fuzzcontract::{
+    FakeVal, TypedFuzzInstruction, TypedModBuf, TypedModCrypto, TypedModInt, TypedModMap,
+    TypedModVec,
+};
+use rand::Rng;
+use soroban_sdk::xdr::{ScMap, ScMapEntry, ScVal};
+use soroban_sdk::{Bytes, Env, IntoVal, Map, TryFromVal, Val, Vec, U256};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SweepTarget {
+    BytesAppend,
+    BytesNewFromLinearMemory,
+    ComputeHashSha256,
+    MapGet,
+    MapPut,
+    U256Pow,
+    VecAppend,
+    VecInsert,
+    VecPushBack,
+}
+
+impl FromStr for SweepTarget {
+    type Err = String;
+
+    // Takes the names used in the `Syscalls` column.
+    fn from_str(s: &str) -> Result<SweepTarget, String> {
+        [
+            SweepTarget::BytesAppend,
+            SweepTarget::BytesNewFromLinearMemory,
+            SweepTarget::ComputeHashSha256,
+            SweepTarget::MapGet,
+            SweepTarget::MapPut,
+            SweepTarget::U256Pow,
+            SweepTarget::VecAppend,
+            SweepTarget::VecInsert,
+            SweepTarget::VecPushBack,
+        ]
+        .into_iter()
+        .find(|target| target.name() == s)
+        .ok_or_else(|| format!("can't sweep {}", s))
+    }
+}
+
+impl SweepTarget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SweepTarget::BytesAppend => "syscalls::buf::bytes_append",
+            SweepTarget::BytesNewFromLinearMemory => "syscalls::buf::bytes_new_from_linear_memory",
+            SweepTarget::ComputeHashSha256 => "syscalls::crypto::compute_hash_sha256",
+            SweepTarget::MapGet => "syscalls::map::map_get",
+            SweepTarget::MapPut => "syscalls::map::map_put",
+            SweepTarget::U256Pow => "syscalls::int::u256_pow",
+            SweepTarget::VecAppend => "syscalls::vec::vec_append",
+            SweepTarget::VecInsert => "syscalls::vec::vec_insert",
+            SweepTarget::VecPushBack => "syscalls::vec::vec_push_back",
+        }
+    }
+
+    // `size` is the byte length, element count or exponent, depending on
+    // the syscall. Contents come from `rng`, so a seed gives the same input
+    // shape at every size.
+    pub fn instruction(&self, env: &Env, rng: &mut impl Rng, size: u32) -> TypedFuzzInstruction {
+        let bytes = |rng: &mut dyn rand::RngCore| {
+            let mut data = vec![0u8; size as usize];
+            rng.fill_bytes(&mut data);
+            Bytes::from_slice(env, &data)
+        };
+        let val = |rng: &mut dyn rand::RngCore| -> Val { rng.next_u32().into_val(env) };
+        // Built in one host call rather than a `push_back` per element.
+        let vec = |rng: &mut dyn rand::RngCore| {
+            let vals: std::vec::Vec<Val> = (0..size).map(|_| val(rng)).collect();
+            Vec::from_slice(env, &vals)
+        };
+        // Even keys, so an odd key is guaranteed to be new. Converted from XDR
+        // in one go rather than a `set` per key.
+        let map = || {
+            let entries: std::vec::Vec<ScMapEntry> = (0..size)
+                .map(|key| ScMapEntry {
+                    key: ScVal::U32(key * 2),
+                    val: ScVal::U32(key),
+                })
+                .collect();
+            let map = ScVal::Map(Some(ScMap(entries.try_into().unwrap())));
+            let map = Val::try_from_val(env, &map).unwrap();
+            Map::<Val, Val>::try_from_val(env, &map).unwrap()
+        };
+        let fake = |v: Val| FakeVal(v.get_payload());
+
+        match self {
+            SweepTarget::BytesAppend => {
+                TypedFuzzInstruction::Buf(TypedModBuf::BytesAppend(bytes(rng), bytes(rng)))
+            }
+            // Fails once `size` runs past the guest's linear memory.
+            SweepTarget::BytesNewFromLinearMemory => {
+                TypedFuzzInstruction::Buf(TypedModBuf::BytesNewFromLinearMemory(0, size))
+            }
+            SweepTarget::ComputeHashSha256 => {
+                TypedFuzzInstruction::Crypto(TypedModCrypto::ComputeHashSha256(bytes(rng)))
+            }
+            SweepTarget::MapGet => {
+                let key = rng.gen_range(0..size.max(1)) * 2;
+                TypedFuzzInstruction::Map(TypedModMap::MapGet(map(), fake(key.into_val(env))))
+            }
+            SweepTarget::MapPut => {
+                let key = rng.gen_range(0..size.max(1)) * 2 + 1;
+                TypedFuzzInstruction::Map(TypedModMap::MapPut(
+                    map(),
+                    fake(key.into_val(env)),
+                    fake(val(rng)),
+                ))
+            }
+            // A base of 1 never overflows, so every exponent runs to completion.
+            SweepTarget::U256Pow => {
+                let base: Val = U256::from_u32(env, 1).into_val(env);
+                TypedFuzzInstruction::Int(TypedModInt::U256Pow(fake(base), size))
+            }
+            SweepTarget::VecAppend => {
+                TypedFuzzInstruction::Vec(TypedModVec::VecAppend(vec(rng), vec(rng)))
+            }
+            SweepTarget::VecInsert => {
+                let index = rng.gen_range(0..=size);
+                TypedFuzzInstruction::Vec(TypedModVec::VecInsert(vec(rng), index, fake(val(rng))))
+            }
+            SweepTarget::VecPushBack => {
+                TypedFuzzInstruction::Vec(TypedModVec::VecPushBack(vec(rng), fake(val(rng))))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SweepConfig {
+    pub target: SweepTarget,
+    pub min: u32,
+    pub max: u32,
+    pub factor: f64,
+}
+
+impl SweepConfig {
+    // `min`, `min * factor`, ... up to and including `max`.
+    pub fn sizes(&self) -> std::vec::Vec<u32> {
+        let mut sizes = std::vec::Vec::new();
+        let mut size = self.min as f64;
+        while size <= self.max as f64 {
+            let rounded = size.round() as u32;
+            if sizes.last() != Some(&rounded) {
+                sizes.push(rounded);
+            }
+            size *= self.factor;
+        }
+        if sizes.last() != Some(&self.max) {
+            sizes.push(self.max);
+        }
+        sizes
+    }
+}
@@ -0,0 +1,365 @@
+// The single definition of every host function the guest can call. From it,
+// `syscalls!` generates the `Arbitrary` prototype enums (in table order, so
+// seeds keep generating the same inputs), their conversion to the guest's
+// `TypedFuzzInstruction`, and the canonical syscall names used in the CSV.
+//
+// A new host function is one row here. The generated `guest_syscall_name`
+// matches the guest's enums exhaustively, so a guest variant without a row
+// fails to compile, and a row whose arguments don't match its guest variant
+// fails in `to_guest`.
+
+use crate::fuzzcontract::*;
+use soroban_sdk::arbitrary::arbitrary;
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::{Address, Bytes, Env, FromVal, Map, String, Symbol, Val, Vec};
+
+// How a prototype field becomes the argument the guest expects.
+trait GuestArg: SorobanArbitrary {
+    type Guest;
+
+    fn to_guest(env: &Env, prototype: &Self::Prototype) -> Self::Guest;
+}
+
+macro_rules! object_args {
+    ($($ty:ty),*) => {
+        $(
+            impl GuestArg for $ty {
+                type Guest = $ty;
+
+                fn to_guest(env: &Env, prototype: &Self::Prototype) -> $ty {
+                    <$ty>::from_val(env, prototype)
+                }
+            }
+        )*
+    };
+}
+
+// Primitive prototypes are the values themselves.
+macro_rules! primitive_args {
+    ($($ty:ty),*) => {
+        $(
+            impl GuestArg for $ty {
+                type Guest = $ty;
+
+                fn to_guest(_env: &Env, prototype: &Self::Prototype) -> $ty {
+                    *prototype
+                }
+            }
+        )*
+    };
+}
+
+object_args!(Address, Bytes, Map<Val, Val>, String, Symbol, Vec<Val>);
+primitive_args!(i64, i128, u32, u64, u128);
+
+// The guest takes untyped values as raw payloads, so they reach the host
+// without the SDK checking their type.
+impl GuestArg for Val {
+    type Guest = FakeVal;
+
+    fn to_guest(env: &Env, prototype: &Self::Prototype) -> FakeVal {
+        FakeVal(Val::from_val(env, prototype).get_payload())
+    }
+}
+
+macro_rules! syscalls {
+    (@unit $instruction:expr, $name:literal) => {
+        $instruction
+    };
+    (
+        $(
+            $module:ident
+            $( ($proto:ident, $guest:ident, $prefix:literal) {
+                $( $variant:ident $(( $($arg:ident: $ty:ty),+ ))? => $name:literal, )*
+            } )?
+            $( => $unit_name:literal )?,
+        )*
+    ) => {
+        #[derive(Clone, Debug, arbitrary::Arbitrary)]
+        pub enum TypedFuzzInstructionPrototype {
+            $( $module $( ($proto) )?, )*
+        }
+
+        $( $(
+            #[derive(Clone, Debug, arbitrary::Arbitrary)]
+            pub enum $proto {
+                $( $variant $(( $(<$ty as SorobanArbitrary>::Prototype),+ ))?, )*
+            }
+        )? )*
+
+        impl TypedFuzzInstructionPrototype {
+            pub fn to_guest(&self, env: &Env) -> TypedFuzzInstruction {
+                match self {
+                    $(
+                        $( Self::$module(v) => TypedFuzzInstruction::$module(match v {
+                            $(
+                                $proto::$variant $(( $($arg),+ ))? => $guest::$variant $((
+                                    $(<$ty as GuestArg>::to_guest(env, $arg)),+
+                                ))?,
+                            )*
+                        }), )?
+                        $( Self::$module => {
+                            syscalls!(@unit TypedFuzzInstruction::$module, $unit_name)
+                        } )?
+                    )*
+                }
+            }
+        }
+
+        pub fn get_syscall_name_only(input: &TypedFuzzInstructionPrototype) -> &'static str {
+            match input {
+                $(
+                    $( TypedFuzzInstructionPrototype::$module(v) => match v {
+                        $(
+                            $proto::$variant { .. } => concat!("syscalls::", $prefix, "::", $name),
+                        )*
+                    }, )?
+                    $( TypedFuzzInstructionPrototype::$module => $unit_name, )?
+                )*
+            }
+        }
+
+        #[cfg(test)]
+        const SYSCALL_NAMES: &[&str] = &[
+            $(
+                $( $( concat!("syscalls::", $prefix, "::", $name), )* )?
+                $( $unit_name, )?
+            )*
+        ];
+
+        pub fn guest_syscall_name(instruction: &TypedFuzzInstruction) -> &'static str {
+            match instruction {
+                $(
+                    $( TypedFuzzInstruction::$module(v) => match v {
+                        $(
+                            $guest::$variant { .. } => concat!("syscalls::", $prefix, "::", $name),
+                        )*
+                    }, )?
+                    $( TypedFuzzInstruction::$module => $unit_name, )?
+                )*
+            }
+        }
+    };
+}
+
+syscalls! {
+    Address(TypedModAddressPrototype, TypedModAddress, "address") {
+        AccountPublicKeyToAddress(a: Bytes) => "account_public_key_to_address",
+        AddressToAccountPublicKey(a: Address) => "address_to_account_public_key",
+        AddressToContractId(a: Address) => "address_to_contract_id",
+        AuthorizeAsCurrContract(a: Vec<Val>) => "authorize_as_curr_contract",
+        ContractIdToAddress(a: Bytes) => "contract_id_to_address",
+        RequireAuth(a: Address) => "require_auth",
+        RequireAuthForArgs(a: Address, b: Vec<Val>) => "require_auth_for_args",
+    },
+    Buf(TypedModBufPrototype, TypedModBuf, "buf") {
+        BytesAppend(a: Bytes, b: Bytes) => "bytes_append",
+        BytesBack(a: Bytes) => "bytes_back",
+        BytesCopyFromLinearMemory(a: Bytes, b: u32, c: u32, d: u32)
+            => "bytes_copy_from_linear_memory",
+        BytesCopyToLinearMemory(a: Bytes, b: u32, c: u32, d: u32) => "bytes_copy_to_linear_memory",
+        BytesDel(a: Bytes, b: u32) => "bytes_del",
+        BytesFront(a: Bytes) => "bytes_front",
+        BytesGet(a: Bytes, b: u32) => "bytes_get",
+        BytesInsert(a: Bytes, b: u32, c: u32) => "bytes_insert",
+        BytesLen(a: Bytes) => "bytes_len",
+        BytesNew => "bytes_new",
+        BytesNewFromLinearMemory(a: u32, b: u32) => "bytes_new_from_linear_memory",
+        BytesPop(a: Bytes) => "bytes_pop",
+        BytesPush(a: Bytes, b: u32) => "bytes_push",
+        BytesPut(a: Bytes, b: u32, c: u32) => "bytes_put",
+        BytesSlice(a: Bytes, b: u32, c: u32) => "bytes_slice",
+        DeserializeFromBytes(a: Bytes) => "deserialize_from_bytes",
+        SerializeToBytes(a: Val) => "serialize_to_bytes",
+        StringCopyToLinearMemory(a: String, b: u32, c: u32, d: u32)
+            => "string_copy_to_linear_memory",
+        StringLen(a: String) => "string_len",
+        StringNewFromLinearMemory(a: u32, b: u32) => "string_new_from_linear_memory",
+        SymbolCopyToLinearMemory(a: Symbol, b: u32, c: u32, d: u32)
+            => "symbol_copy_to_linear_memory",
+        SymbolIndexInLinearMemory(a: Symbol, b: u32, c: u32) => "symbol_index_in_linear_memory",
+        SymbolLen(a: Symbol) => "symbol_len",
+        SymbolNewFromLinearMemory(a: u32, b: u32) => "symbol_new_from_linear_memory",
+    },
+    Call(TypedModCallPrototype, TypedModCall, "call") {
+        Call(a: Address, b: Symbol, c: Vec<Val>) => "call",
+        TryCall(a: Address, b: Symbol, c: Vec<Val>) => "try_call",
+    },
+    Context(TypedModContextPrototype, TypedModContext, "context") {
+        ContractEvent(a: Vec<Val>, b: Val) => "contract_event",
+        FailWithError(a: Val) => "fail_with_error",
+        GetCurrentCallStack => "get_current_call_stack",
+        GetCurrentContractAddress => "get_current_contract_address",
+        GetInvokingContract => "get_invoking_contract",
+        GetLedgerNetworkId => "get_ledger_network_id",
+        GetLedgerSequence => "get_ledger_sequence",
+        GetLedgerTimestamp => "get_ledger_timestamp",
+        GetLedgerVersion => "get_ledger_version",
+        LogFromLinearMemory(a: u32, b: u32, c: u32, d: u32) => "log_from_linear_memory",
+        ObjCmp(a: Val, b: Val) => "obj_cmp",
+    },
+    Crypto(TypedModCryptoPrototype, TypedModCrypto, "crypto") {
+        ComputeHashKeccak256(a: Bytes) => "compute_hash_keccak256",
+        ComputeHashSha256(a: Bytes) => "compute_hash_sha256",
+        RecoverKeyEcdsaSecp256k1(a: Bytes, b: Bytes, c: u32) => "recover_key_ecdsa_secp256k1",
+        VerifySigEd25519(a: Bytes, b: Bytes, c: Bytes) => "verify_sig_ed25519",
+    },
+    Int(TypedModIntPrototype, TypedModInt, "int") {
+        DurationObjFromU64(a: u64) => "duration_obj_from_u64",
+        DurationObjToU64(a: Val) => "duration_obj_to_u64",
+        I256Add(a: Val, b: Val) => "i256_add",
+        I256Div(a: Val, b: Val) => "i256_div",
+        I256Mul(a: Val, b: Val) => "i256_mul",
+        I256ObjFromBeBytes(a: Bytes) => "i256_val_from_be_bytes",
+        I256ObjToBeBytes(a: Val) => "i256_val_to_be_bytes",
+        I256Pow(a: Val, b: u32) => "i256_pow",
+        I256Shl(a: Val, b: u32) => "i256_shl",
+        I256Shr(a: Val, b: u32) => "i256_shr",
+        I256Sub(a: Val, b: Val) => "i256_sub",
+        ObjFromI64(a: i64) => "obj_from_i64",
+        ObjFromI128Pieces(a: i64, b: u64) => "obj_from_i128_pieces",
+        ObjFromI256Pieces(a: i64, b: u64, c: u64, d: u64) => "obj_from_i256_pieces",
+        ObjFromU64(a: u64) => "obj_from_u64",
+        ObjFromU128Pieces(a: u64, b: u64) => "obj_from_u128_pieces",
+        ObjFromU256Pieces(a: u64, b: u64, c: u64, d: u64) => "obj_from_u256_pieces",
+        ObjToI64(a: i64) => "obj_to_i64",
+        ObjToI128Hi64(a: i128) => "obj_to_i128_hi64",
+        ObjToI128Lo64(a: i128) => "obj_to_i128_lo64",
+        ObjToI256HiHi(a: Val) => "obj_to_i256_hi_hi",
+        ObjToI256HiLo(a: Val) => "obj_to_i256_hi_lo",
+        ObjToI256LoHi(a: Val) => "obj_to_i256_lo_hi",
+        ObjToI256LoLo(a: Val) => "obj_to_i256_lo_lo",
+        ObjToU64(a: u64) => "obj_to_u64",
+        ObjToU128Hi64(a: u128) => "obj_to_u128_hi64",
+        ObjToU128Lo64(a: u128) => "obj_to_u128_lo64",
+        ObjToU256HiHi(a: Val) => "obj_to_u256_hi_hi",
+        ObjToU256HiLo(a: Val) => "obj_to_u256_hi_lo",
+        ObjToU256LoHi(a: Val) => "obj_to_u256_lo_hi",
+        ObjToU256LoLo(a: Val) => "obj_to_u256_lo_lo",
+        TimepointObjFromU64(a: u64) => "timepoint_obj_from_u64",
+        TimepointObjToU64(a: Val) => "timepoint_obj_to_u64",
+        U256Add(a: Val, b: Val) => "u256_add",
+        U256Div(a: Val, b: Val) => "u256_div",
+        U256Mul(a: Val, b: Val) => "u256_mul",
+        U256ValFromBeBytes(a: Bytes) => "u256_val_from_be_bytes",
+        U256ValToBeBytes(a: Val) => "u256_val_to_be_bytes",
+        U256Pow(a: Val, b: u32) => "u256_pow",
+        U256Shl(a: Val, b: u32) => "u256_shl",
+        U256Shr(a: Val, b: u32) => "u256_shr",
+        U256Sub(a: Val, b: Val) => "u256_sub",
+    },
+    Ledger(TypedModLedgerPrototype, TypedModLedger, "ledger") {
+        BumpContractData(a: Val, b: u32) => "bump_contract_data",
+        CreateAssetContract(a: Bytes) => "create_asset_contract",
+        CreateContract(a: Address, b: Bytes, c: Bytes) => "create_contract",
+        DelContractData(a: Val) => "del_contract_data",
+        GetAssetContractId(a: Bytes) => "get_asset_contract_id",
+        GetContractData(a: Val) => "get_contract_data",
+        GetContractId(a: Address, b: Bytes) => "get_contract_id",
+        HasContractData(a: Val) => "has_contract_data",
+        PutContractData(a: Val, b: Val, c: Val) => "put_contract_data",
+        UpdateCurrentContractWasm(a: Bytes) => "update_current_contract_wasm",
+        UploadWasm(a: Bytes) => "upload_wasm",
+    },
+    Map(TypedModMapPrototype, TypedModMap, "map") {
+        MapDel(a: Map<Val, Val>, b: Val) => "map_del",
+        MapGet(a: Map<Val, Val>, b: Val) => "map_get",
+        MapHas(a: Map<Val, Val>, b: Val) => "map_has",
+        MapKeys(a: Map<Val, Val>) => "map_keys",
+        MapLen(a: Map<Val, Val>) => "map_len",
+        MapMaxKey(a: Map<Val, Val>) => "map_max_key",
+        MapMinKey(a: Map<Val, Val>) => "map_min_key",
+        MapNew => "map_new",
+        MapNewFromLinearMemory(a: u32, b: u32, c: u32) => "map_new_from_linear_memory",
+        MapNextKey(a: Map<Val, Val>, b: Val) => "map_next_key",
+        MapPrevKey(a: Map<Val, Val>, b: Val) => "map_prev_key",
+        MapPut(a: Map<Val, Val>, b: Val, c: Val) => "map_put",
+        MapUnpackToLinearMemory(a: Map<Val, Val>, b: u32, c: u32, d: u32)
+            => "map_unpack_to_linear_memory",
+        MapValues(a: Map<Val, Val>) => "map_values",
+    },
+    Prng(TypedModPrngPrototype, TypedModPrng, "prng") {
+        PrngBytesNew(a: u32) => "prng_bytes_new",
+        PrngReseed(a: Bytes) => "prng_reseed",
+        PrngU64InInclusiveRange(a: u64, b: u64) => "prng_u64_in_inclusive_range",
+        PrngVecShuffle(a: Vec<Val>) => "prng_vec_shuffle",
+    },
+    Test => "syscalls::test::dummy0",
+    Vec(TypedModVecPrototype, TypedModVec, "vec") {
+        VecAppend(a: Vec<Val>, b: Vec<Val>) => "vec_append",
+        VecBack(a: Vec<Val>) => "vec_back",
+        VecBinarySearch(a: Vec<Val>, b: Val) => "vec_binary_search",
+        VecDel(a: Vec<Val>, b: u32) => "vec_del",
+        VecFirstIndexOf(a: Vec<Val>, b: Val) => "vec_first_index_of",
+        VecFront(a: Vec<Val>) => "vec_front",
+        VecGet(a: Vec<Val>, b: u32) => "vec_get",
+        VecInsert(a: Vec<Val>, b: u32, c: Val) => "vec_insert",
+        VecLastIndexOf(a: Vec<Val>, b: Val) => "vec_last_index_of",
+        VecLen(a: Vec<Val>) => "vec_len",
+        VecNew(a: Val) => "vec_new",
+        VecNewFromLinearMemory(a: u32, b: u32) => "vec_new_from_linear_memory",
+        VecPopBack(a: Vec<Val>) => "vec_pop_back",
+        VecPopFront(a: Vec<Val>) => "vec_pop_front",
+        VecPushBack(a: Vec<Val>, b: Val) => "vec_push_back",
+        VecPushFront(a: Vec<Val>, b: Val) => "vec_push_front",
+        VecPut(a: Vec<Val>, b: u32, c: Val) => "vec_put",
+        VecSlice(a: Vec<Val>, b: u32, c: u32) => "vec_slice",
+        VecUnpackToLinearMemory(a: Vec<Val>, b: u32, c: u32) => "vec_unpack_to_linear_memory",
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SYSCALL_NAMES;
+    use soroban_env_common::call_macro_with_all_host_functions;
+
+    macro_rules! host_function_names {
+        {
+            $(
+                $(#[$mod_attr:meta])*
+                mod $mod_id:ident $mod_str:literal
+                {
+                    $(
+                        $(#[$fn_attr:meta])*
+                        { $fn_str:literal, fn $fn_id:ident ($($arg:ident: $type:ty),*) -> $ret:ty }
+                    )*
+                }
+            )*
+        } => {
+            const HOST_FUNCTION_NAMES: &[&str] = &[
+                $( $( concat!("syscalls::", stringify!($mod_id), "::", stringify!($fn_id)), )* )*
+            ];
+        };
+    }
+
+    call_macro_with_all_host_functions! { host_function_names }
+
+    // Exhaustive matching only catches guest variants without a row; a host
+    // function the guest has no variant for shows up here.
+    #[test]
+    fn rows_match_host_functions() {
+        let host = HOST_FUNCTION_NAMES;
+
+        let without_row: Vec<&str> = host
+            .iter()
+            .copied()
+            .filter(|name| !SYSCALL_NAMES.contains(name))
+            .collect();
+        assert!(
+            without_row.is_empty(),
+            "host functions without a guest variant: {:?}",
+            without_row
+        );
+
+        let unknown: Vec<&str> = SYSCALL_NAMES
+            .iter()
+            .copied()
+            .filter(|name| !host.contains(name))
+            .collect();
+        assert!(
+            unknown.is_empty(),
+            "rows naming no host function: {:?}",
+            unknown
+        );
+    }
+}
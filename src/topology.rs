@@ -0,0 +1,79 @@
+// Extra fuzz contract instances for `Call` and `TryCall` to target.
+
+use crate::fuzzcontract::{self, FuzzInstruction, TypedFuzzInstruction, TypedModCall};
+use rand::Rng;
+use soroban_sdk::{Address, Env, IntoVal, Symbol, Val, Vec};
+
+#[derive(Clone, Debug)]
+pub struct TopologyConfig {
+    pub contracts: u32,
+    // Number of cross-contract frames below the measured contract.
+    pub call_depth: u32,
+    // Probability that a generated call is redirected to a registered instance.
+    pub hit_rate: f64,
+}
+
+pub struct CallTopology {
+    config: TopologyConfig,
+    // Includes the measured contract itself, so some calls re-enter it.
+    instances: std::vec::Vec<Address>,
+}
+
+impl CallTopology {
+    pub fn new(env: &Env, contract_id: &Address, config: &TopologyConfig) -> CallTopology {
+        let mut instances = vec![contract_id.clone()];
+        for _ in 0..config.contracts {
+            instances.push(env.register_contract_wasm(None, fuzzcontract::WASM));
+        }
+        CallTopology {
+            config: config.clone(),
+            instances,
+        }
+    }
+
+    fn pick(&self, rng: &mut impl Rng) -> Address {
+        self.instances[rng.gen_range(0..self.instances.len())].clone()
+    }
+
+    fn call(
+        try_call: bool,
+        address: Address,
+        func: Symbol,
+        args: Vec<Val>,
+    ) -> TypedFuzzInstruction {
+        if try_call {
+            TypedFuzzInstruction::Call(TypedModCall::TryCall(address, func, args))
+        } else {
+            TypedFuzzInstruction::Call(TypedModCall::Call(address, func, args))
+        }
+    }
+
+    // Rewrites a call into a chain of `run` invocations `call_depth` frames
+    // deep, ending in the no-op `Test` instruction.
+    pub fn bias(
+        &self,
+        env: &Env,
+        rng: &mut impl Rng,
+        instruction: TypedFuzzInstruction,
+    ) -> TypedFuzzInstruction {
+        let try_call = match &instruction {
+            TypedFuzzInstruction::Call(TypedModCall::Call(..)) => false,
+            TypedFuzzInstruction::Call(TypedModCall::TryCall(..)) => true,
+            _ => return instruction,
+        };
+        if !rng.gen_bool(self.config.hit_rate) {
+            return instruction;
+        }
+
+        let run = Symbol::new(env, "run");
+
+        let mut inner = TypedFuzzInstruction::Test;
+        for _ in 1..self.config.call_depth {
+            let args = Vec::from_array(env, [FuzzInstruction::Typed(inner).into_val(env)]);
+            inner = Self::call(try_call, self.pick(rng), run.clone(), args);
+        }
+
+        let args = Vec::from_array(env, [FuzzInstruction::Typed(inner).into_val(env)]);
+        Self::call(try_call, self.pick(rng), run, args)
+    }
+}
@@ -0,0 +1,200 @@
+// Nested `Vec`/`Map` trees for the syscalls charged by visiting objects:
+// `ObjCmp`, `SerializeToBytes` and the map and vector lookups comparing
+// their keys or elements. Arbitrary values are shallow, so these are built
+// to a chosen depth and breadth; the node count goes in the `Size` column.
+
+use crate::fuzzcontract::{
+    FakeVal, TypedFuzzInstruction, TypedModBuf, TypedModContext, TypedModMap, TypedModVec,
+};
+use crate::generator::Generated;
+use rand::rngs::StdRng;
+use rand::Rng;
+use soroban_sdk::{Bytes, Env, IntoVal, Map, Symbol, Val, Vec};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Leaf {
+    // Values stored in the `Val` itself: void, bools and `u32`s.
+    Small,
+    // 64- and 128-bit integers too large for a small value.
+    Int,
+    Bytes,
+    Symbol,
+}
+
+impl FromStr for Leaf {
+    type Err = std::string::String;
+
+    fn from_str(s: &str) -> Result<Leaf, std::string::String> {
+        match s {
+            "small" => Ok(Leaf::Small),
+            "int" => Ok(Leaf::Int),
+            "bytes" => Ok(Leaf::Bytes),
+            "symbol" => Ok(Leaf::Symbol),
+            _ => Err(format!("unknown leaf kind: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ValTreeConfig {
+    // Maximum nesting of each tree; a leaf is depth 0.
+    pub depth: u32,
+    // Maximum number of elements per vector or map.
+    pub breadth: u32,
+    // Leaves are drawn evenly from these kinds.
+    pub leaves: std::vec::Vec<Leaf>,
+}
+
+impl Default for ValTreeConfig {
+    fn default() -> ValTreeConfig {
+        ValTreeConfig {
+            depth: 4,
+            breadth: 8,
+            leaves: vec![Leaf::Small, Leaf::Int, Leaf::Bytes, Leaf::Symbol],
+        }
+    }
+}
+
+struct Builder<'a> {
+    env: &'a Env,
+    config: &'a ValTreeConfig,
+    nodes: u64,
+}
+
+impl Builder<'_> {
+    fn tree(&mut self, rng: &mut StdRng, depth: u32) -> Val {
+        self.nodes += 1;
+        if depth == 0 || rng.gen_bool(0.25) {
+            return self.leaf(rng);
+        }
+
+        let len = rng.gen_range(0..=self.config.breadth);
+        if rng.gen() {
+            let mut vec = Vec::new(self.env);
+            for _ in 0..len {
+                vec.push_back(self.tree(rng, depth - 1));
+            }
+            vec.into_val(self.env)
+        } else {
+            let mut map = Map::new(self.env);
+            for _ in 0..len {
+                let key = self.tree(rng, depth - 1);
+                let val = self.tree(rng, depth - 1);
+                map.set(key, val);
+            }
+            map.into_val(self.env)
+        }
+    }
+
+    fn leaf(&self, rng: &mut StdRng) -> Val {
+        let env = self.env;
+        match self.config.leaves[rng.gen_range(0..self.config.leaves.len())] {
+            Leaf::Small => match rng.gen_range(0..3) {
+                0 => ().into_val(env),
+                1 => rng.gen::<bool>().into_val(env),
+                _ => rng.gen::<u32>().into_val(env),
+            },
+            Leaf::Int => match rng.gen_range(0..3) {
+                0 => (rng.gen::<u64>() | 1 << 63).into_val(env),
+                1 => rng.gen::<i128>().into_val(env),
+                _ => rng.gen::<u128>().into_val(env),
+            },
+            Leaf::Bytes => {
+                let mut data = vec![0u8; rng.gen_range(0..=64)];
+                rng.fill(&mut data[..]);
+                Bytes::from_slice(env, &data).into_val(env)
+            }
+            Leaf::Symbol => {
+                const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_";
+                let symbol: std::vec::Vec<u8> = (0..rng.gen_range(1..=32))
+                    .map(|_| CHARS[rng.gen_range(0..CHARS.len())])
+                    .collect();
+                Symbol::new(env, std::str::from_utf8(&symbol).unwrap()).into_val(env)
+            }
+        }
+    }
+
+    // `len` trees, plus a needle that is a separately built copy of one of
+    // them or, with `equal` unset, a tree of its own.
+    fn haystack(&mut self, rng: &mut StdRng, len: u32, equal: bool) -> (Vec<Val>, Val) {
+        let depth = self.config.depth.saturating_sub(1);
+        let copied = rng.gen_range(0..len.max(1));
+
+        let mut copy_rng = None;
+        let mut trees = Vec::new(self.env);
+        for index in 0..len {
+            if index == copied {
+                copy_rng = Some(rng.clone());
+            }
+            trees.push_back(self.tree(rng, depth));
+        }
+        let needle = match copy_rng {
+            Some(mut copy_rng) if equal => self.tree(&mut copy_rng, depth),
+            _ => self.tree(rng, depth),
+        };
+        (trees, needle)
+    }
+}
+
+pub fn generate(env: &Env, rng: &mut StdRng, config: &ValTreeConfig) -> Generated {
+    let mut builder = Builder {
+        env,
+        config,
+        nodes: 0,
+    };
+    let fake = |v: Val| FakeVal(v.get_payload());
+
+    // Comparisons only visit everything when the two sides are equal.
+    let equal = rng.gen();
+    let len = rng.gen_range(1..=config.breadth.max(1));
+
+    let (instruction, shape) = match rng.gen_range(0..5) {
+        0 => {
+            let (mut trees, needle) = builder.haystack(rng, 1, equal);
+            let tree = trees.pop_back().unwrap();
+            let instruction = TypedModContext::ObjCmp(fake(tree), fake(needle));
+            (TypedFuzzInstruction::Context(instruction), "pair")
+        }
+        1 => {
+            let tree = builder.tree(rng, config.depth);
+            let instruction = TypedModBuf::SerializeToBytes(fake(tree));
+            (TypedFuzzInstruction::Buf(instruction), "tree")
+        }
+        2 => {
+            let (trees, needle) = builder.haystack(rng, len, equal);
+            let instruction = TypedModVec::VecFirstIndexOf(trees, fake(needle));
+            (TypedFuzzInstruction::Vec(instruction), "vec of trees")
+        }
+        3 => {
+            let (keys, needle) = builder.haystack(rng, len, equal);
+            let map = keys_to_map(env, keys);
+            let instruction = TypedModMap::MapGet(map, fake(needle));
+            (TypedFuzzInstruction::Map(instruction), "map with tree keys")
+        }
+        _ => {
+            let (keys, needle) = builder.haystack(rng, len, equal);
+            let map = keys_to_map(env, keys);
+            let instruction = TypedModMap::MapPut(map, fake(needle), fake(().into_val(env)));
+            (TypedFuzzInstruction::Map(instruction), "map with tree keys")
+        }
+    };
+
+    Generated {
+        instruction,
+        description: format!(
+            "{} depth<={} equal={} nodes={}",
+            shape, config.depth, equal, builder.nodes
+        ),
+        size: Some(builder.nodes),
+    }
+}
+
+// Equal keys collapse into one entry, which only makes the map smaller.
+fn keys_to_map(env: &Env, keys: Vec<Val>) -> Map<Val, Val> {
+    let mut map = Map::new(env);
+    for key in keys.iter() {
+        map.set(key, ().into_val(env));
+    }
+    map
+}
@@ -0,0 +1,762 @@
+use crate::alloc::{AllocSnapshot, AllocStats};
+use crate::auth::{Account, AuthMode, SignedAuth};
+use crate::config::Config;
+use crate::crypto;
+use crate::custom::CustomContract;
+use crate::fuzzcontract::{self, FuzzInstruction, TypedFuzzInstruction};
+use crate::generator::{Generated, Generator};
+use crate::host::HOST_FUNCTIONS;
+use crate::int256;
+use crate::limits::{self, BudgetLimit, LimitConfig};
+use crate::linmem::{self, LinearMemory};
+use crate::measurement::{DispatchBaseline, Measurement, Outcome};
+use crate::noise::{self, ContextSwitches};
+use crate::outliers::Explanation;
+use crate::perf::{CounterValues, HardwareCounters};
+use crate::program::Program;
+use crate::results;
+use crate::storage::SeededStorage;
+use crate::sweep::SweepConfig;
+use crate::syscalls::{get_syscall_name_only, guest_syscall_name, TypedFuzzInstructionPrototype};
+use crate::topology::CallTopology;
+use crate::valtree::{self, ValTreeConfig};
+use crate::xdr::{self, XdrConfig};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use soroban_sdk::arbitrary::arbitrary::Unstructured;
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::arbitrary::Arbitrary;
+use soroban_sdk::env::internal::DiagnosticLevel;
+use soroban_sdk::testutils::Logs;
+use soroban_sdk::{Address, Env, Val};
+use std::borrow::Cow;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// Runs of the no-op instruction behind the `--isolate-host` baseline.
+const DISPATCH_SAMPLES: usize = 101;
+
+// Each worker owns its own `Env`, so budget figures never mix between threads.
+pub struct Worker {
+    env: Env,
+    contract_id: Address,
+    index: u64,
+    contended: bool,
+    counters: Option<HardwareCounters>,
+    // Only present when running with `--isolate-host`.
+    dispatch: Option<DispatchBaseline>,
+    storage: Option<SeededStorage>,
+    auth: Option<SignedAuth>,
+    topology: Option<CallTopology>,
+    custom: Option<CustomContract>,
+    keep_panics: bool,
+    track_alloc: bool,
+    generator: Generator,
+    message_len: Option<u32>,
+    int_bits: Option<u32>,
+    xdr: XdrConfig,
+    val_tree: ValTreeConfig,
+    linear_memory: Option<LinearMemory>,
+}
+
+impl Worker {
+    pub fn new(config: &Config, index: u64) -> Worker {
+        let (env, accounts) = match config.auth {
+            AuthMode::Signed => {
+                let mut rng = StdRng::seed_from_u64(config.seed);
+                let accounts: Vec<Account> = (0..config.auth_accounts)
+                    .map(|_| Account::generate(&mut rng))
+                    .collect();
+                (
+                    Env::from_snapshot(SignedAuth::snapshot(&accounts)),
+                    accounts,
+                )
+            }
+            AuthMode::None | AuthMode::MockAll => (Env::default(), Vec::new()),
+        };
+
+        Worker::setup(config, index, env, accounts)
+    }
+
+    // For embedding: signed auth needs accounts in the ledger snapshot the
+    // `Env` starts from, so it isn't available here. Panics are kept, since
+    // every input must produce a measurement.
+    pub fn with_env(config: &Config, env: Env) -> Worker {
+        assert!(
+            config.auth != AuthMode::Signed,
+            "signed auth needs an Env created by the worker"
+        );
+        let mut worker = Worker::setup(config, 0, env, Vec::new());
+        worker.keep_panics = true;
+        worker
+    }
+
+    fn setup(config: &Config, index: u64, env: Env, accounts: Vec<Account>) -> Worker {
+        if config.auth == AuthMode::MockAll {
+            env.mock_all_auths();
+        }
+
+        let contract_id = env.register_contract_wasm(None, fuzzcontract::WASM);
+
+        let custom = config
+            .custom_wasm
+            .as_ref()
+            .map(|(wasm, spec)| CustomContract::load(&env, wasm, spec));
+
+        let topology = config
+            .topology
+            .as_ref()
+            .map(|topology| CallTopology::new(&env, &contract_id, topology));
+
+        let auth = match config.auth {
+            AuthMode::Signed => Some(SignedAuth::new(accounts, &contract_id)),
+            AuthMode::None | AuthMode::MockAll => None,
+        };
+
+        let storage = config
+            .storage
+            .as_ref()
+            .map(|storage| SeededStorage::new(&env, &contract_id, storage));
+
+        // Without access to perf events, rows just have no counters.
+        let counters = if config.perf_counters {
+            HardwareCounters::open()
+        } else {
+            None
+        };
+
+        let mut worker = Worker {
+            env,
+            contract_id,
+            index,
+            contended: config.is_contended(),
+            counters,
+            dispatch: None,
+            storage,
+            auth,
+            topology,
+            custom,
+            keep_panics: config.keep_panics || config.limits.is_some(),
+            track_alloc: config.track_alloc,
+            generator: config.generator,
+            message_len: config.message_len,
+            int_bits: config.int_bits,
+            xdr: config.xdr.clone(),
+            val_tree: config.val_tree.clone(),
+            linear_memory: (config.generator == Generator::LinearMemory)
+                .then(|| LinearMemory::of(fuzzcontract::WASM)),
+        };
+        if config.isolate_host {
+            worker.dispatch = Some(worker.dispatch_baseline());
+        }
+        worker
+    }
+
+    // The shared dispatch overhead, from running the no-op `Test`
+    // instruction. One run's wall time is noisier than the differences it is
+    // subtracted from, so each figure is the median of `DISPATCH_SAMPLES`
+    // runs, taken once per worker.
+    fn dispatch_baseline(&self) -> DispatchBaseline {
+        let env = &self.env;
+        let client = fuzzcontract::Client::new(env, &self.contract_id);
+
+        let fuzz_instruction = FuzzInstruction::Typed(TypedFuzzInstruction::Test);
+
+        let samples: Vec<DispatchBaseline> = (0..DISPATCH_SAMPLES)
+            .map(|_| {
+                env.budget().reset_unlimited();
+
+                let before = Instant::now();
+                let panic_r = fuzz_catch_panic(|| {
+                    let _call_r = client.try_run(&fuzz_instruction);
+                });
+                let duration = before.elapsed();
+                assert!(panic_r.is_ok(), "the no-op `Test` instruction panicked");
+
+                DispatchBaseline {
+                    cpu_instruction_cost: env.budget().cpu_instruction_cost(),
+                    memory_bytes_cost: env.budget().memory_bytes_cost(),
+                    duration,
+                }
+            })
+            .collect();
+
+        DispatchBaseline {
+            cpu_instruction_cost: results::median(samples.iter().map(|s| s.cpu_instruction_cost)),
+            memory_bytes_cost: results::median(samples.iter().map(|s| s.memory_bytes_cost)),
+            duration: results::median(samples.iter().map(|s| s.duration)),
+        }
+    }
+
+    pub fn measure_seed(&self, seed: u64) -> Option<Measurement> {
+        self.env.budget().reset_unlimited();
+
+        let mut measurement = self.measure_input(seed, false, None)?;
+        measurement.dispatch = self.dispatch;
+        Some(measurement)
+    }
+
+    // Every step of the program is a separate invocation with its own budget
+    // window; the program only determines the objects each step works on.
+    // Rebuilding those objects grows with the step, so it isn't charged.
+    pub fn measure_program(&self, seed: u64, max_len: usize) -> Vec<Measurement> {
+        let mut raw_data = [0u8; 4096];
+        StdRng::seed_from_u64(seed).fill(&mut raw_data[..]);
+
+        let mut unstructured = Unstructured::new(&raw_data);
+
+        let program = match Program::arbitrary(&mut unstructured, max_len) {
+            Ok(program) => program,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut measurements = Vec::new();
+        for (step, input) in program.instructions().iter().enumerate() {
+            self.env.budget().reset_unlimited();
+
+            // Later steps depend on this one, so stop at the first panic.
+            let mut measurement = match self.measure_prototype(seed, input, true, None) {
+                Some(measurement) => measurement,
+                None => break,
+            };
+            measurement.program_step = Some(step as u64);
+            let panicked = measurement.outcome == Outcome::Panic;
+            measurements.push(measurement);
+            if panicked {
+                break;
+            }
+        }
+        measurements
+    }
+
+    // Runs the input once without limits for its full cost, then again
+    // under each configured limit, recording any that misbehave. Limits only
+    // apply to the call, so conversion is left out of every row.
+    pub fn measure_limits(&self, seed: u64, limits: &LimitConfig) -> Vec<Measurement> {
+        self.env.budget().reset_unlimited();
+
+        let full = match self.measure_input(seed, true, None) {
+            Some(full) => full,
+            None => return Vec::new(),
+        };
+        if full.outcome == Outcome::Panic {
+            return vec![full];
+        }
+
+        let mut measurements = Vec::new();
+        for limit in limits.limits(&full) {
+            self.env.budget().reset_unlimited();
+
+            if let Some(mut measurement) = self.measure_input(seed, true, Some(limit)) {
+                measurement.limit = Some(limit);
+                measurement.problem = limits::problem(&full, &measurement, &limit);
+                measurements.push(measurement);
+            }
+        }
+
+        measurements.insert(0, full);
+        measurements
+    }
+
+    // Every size is a separate invocation; the seed only fills the contents,
+    // so the inputs of one seed differ in size alone. Building the input
+    // charges the budget too, so that part is excluded.
+    pub fn measure_sweep(&self, seed: u64, sweep: &SweepConfig) -> Vec<Measurement> {
+        let env = &self.env;
+        let client = fuzzcontract::Client::new(env, &self.contract_id);
+
+        let mut measurements = Vec::new();
+        for size in sweep.sizes() {
+            env.budget().reset_unlimited();
+
+            let mut rng = StdRng::seed_from_u64(seed);
+            let instruction = sweep.target.instruction(env, &mut rng, size);
+            let syscall_name = guest_syscall_name(&instruction);
+            let fuzz_instruction = FuzzInstruction::Typed(instruction);
+
+            let start = BudgetSnapshot::take(env);
+            let (outcome, timing) = self.timed(|| Outcome::of(&client.try_run(&fuzz_instruction)));
+            if outcome == Outcome::Panic {
+                continue;
+            }
+            let charged = BudgetSnapshot::take(env).since(&start);
+
+            let mut measurement = self.measurement(
+                seed,
+                syscall_name,
+                format!("{:?}", fuzz_instruction),
+                String::new(),
+                charged,
+                outcome,
+                timing,
+            );
+            measurement.size = Some(size as u64);
+            measurements.push(measurement);
+        }
+        measurements
+    }
+
+    // Measures an input given directly rather than generated from a seed;
+    // biasing draws from seed 0.
+    pub fn measure_one(&self, input: &TypedFuzzInstructionPrototype) -> Measurement {
+        self.env.budget().reset_unlimited();
+
+        self.measure_prototype(0, input, false, None).unwrap()
+    }
+
+    // Runs the input of `seed` again with diagnostic events recorded, for
+    // `--explain-outliers`. The charges include recording the events.
+    pub fn explain(&self, seed: u64) -> Option<Explanation> {
+        let env = &self.env;
+        env.host()
+            .set_diagnostic_level(DiagnosticLevel::Debug)
+            .unwrap();
+
+        env.budget().reset_unlimited();
+
+        let measurement = self.measure_input(seed, false, None)?;
+        Some(Explanation {
+            measurement,
+            budget: env.budget().to_string(),
+            events: format!("{:#?}", env.host().get_events().unwrap()),
+            logs: env.logs().all(),
+        })
+    }
+
+    fn measure_input(
+        &self,
+        seed: u64,
+        exclude_conversion: bool,
+        limit: Option<BudgetLimit>,
+    ) -> Option<Measurement> {
+        if let Some(custom) = &self.custom {
+            return self.measure_custom(custom, seed, exclude_conversion, limit);
+        }
+        match self.generator {
+            Generator::Guest => self.measure_guest(seed, exclude_conversion, limit),
+            Generator::Host => self.measure_host(seed, exclude_conversion, limit),
+            Generator::Crypto => self.measure_generated(seed, limit, |env, rng| {
+                crypto::generate(env, rng, self.message_len)
+            }),
+            Generator::Xdr => {
+                self.measure_generated(seed, limit, |env, rng| xdr::generate(env, rng, &self.xdr))
+            }
+            Generator::ValTree => self.measure_generated(seed, limit, |env, rng| {
+                valtree::generate(env, rng, &self.val_tree)
+            }),
+            Generator::LinearMemory => {
+                let memory = self.linear_memory.as_ref().unwrap();
+                self.measure_generated(seed, limit, |env, rng| linmem::generate(env, rng, memory))
+            }
+            Generator::Int256 => self.measure_generated(seed, limit, |env, rng| {
+                int256::generate(env, rng, self.int_bits)
+            }),
+        }
+    }
+
+    fn measure_guest(
+        &self,
+        seed: u64,
+        exclude_conversion: bool,
+        limit: Option<BudgetLimit>,
+    ) -> Option<Measurement> {
+        let mut raw_data = [0u8; 512];
+        StdRng::seed_from_u64(seed).fill(&mut raw_data[..]);
+
+        let mut unstructured = Unstructured::new(&raw_data);
+
+        let input = TypedFuzzInstructionPrototype::arbitrary(&mut unstructured).ok()?;
+
+        self.measure_prototype(seed, &input, exclude_conversion, limit)
+    }
+
+    // Charges are those of the call, plus converting the input to host
+    // objects unless `exclude_conversion` is set. Biasing and preparing the
+    // input are harness work and never charged.
+    fn measure_prototype(
+        &self,
+        seed: u64,
+        input: &TypedFuzzInstructionPrototype,
+        exclude_conversion: bool,
+        limit: Option<BudgetLimit>,
+    ) -> Option<Measurement> {
+        let env = &self.env;
+        let client = fuzzcontract::Client::new(env, &self.contract_id);
+
+        let syscall_name = get_syscall_name_only(input);
+
+        // A separate stream, so biasing doesn't correlate with the bytes that
+        // chose the instruction.
+        let mut rng = StdRng::seed_from_u64(!seed);
+
+        let before = BudgetSnapshot::take(env);
+        let fuzz_instruction = input.to_guest(env);
+        let conversion = self.conversion(&before, exclude_conversion);
+
+        let (fuzz_instruction, storage_entry) = match &self.storage {
+            Some(storage) => storage.bias(env, &mut rng, fuzz_instruction),
+            None => (fuzz_instruction, None),
+        };
+        let fuzz_instruction = match &self.topology {
+            Some(topology) => topology.bias(env, &mut rng, fuzz_instruction),
+            None => fuzz_instruction,
+        };
+        let fuzz_instruction = match &self.auth {
+            Some(auth) => auth.prepare(env, &mut rng, fuzz_instruction),
+            None => fuzz_instruction,
+        };
+        let fuzz_instruction = FuzzInstruction::Typed(fuzz_instruction);
+
+        let start = self.start_call(limit);
+        let (outcome, timing) = self.timed(|| Outcome::of(&client.try_run(&fuzz_instruction)));
+        let charged = self.end_call(&start, limit).plus(&conversion);
+        // Before anything can return, so a panicking `Put` or `Del` doesn't
+        // leave the entry changed for later inputs.
+        if let (Some(storage), Some(index)) = (&self.storage, storage_entry) {
+            storage.restore(env, index);
+        }
+        // Returning an error is ok; panicking is not, unless looking for
+        // exactly that under limits or asked to keep the row.
+        if outcome == Outcome::Panic && !self.keep_panics {
+            return None;
+        }
+
+        Some(self.measurement(
+            seed,
+            syscall_name,
+            format!("{:?}", fuzz_instruction),
+            format!("{:?}", input),
+            charged,
+            outcome,
+            timing,
+        ))
+    }
+
+    // Generators build whole host objects, sometimes an element at a time, so
+    // as in `measure_sweep` building isn't charged: the charges cover the
+    // call, like the duration.
+    fn measure_generated(
+        &self,
+        seed: u64,
+        limit: Option<BudgetLimit>,
+        generate: impl FnOnce(&Env, &mut StdRng) -> Generated,
+    ) -> Option<Measurement> {
+        let env = &self.env;
+        let client = fuzzcontract::Client::new(env, &self.contract_id);
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let generated = generate(env, &mut rng);
+
+        let syscall_name = guest_syscall_name(&generated.instruction);
+        let fuzz_instruction = FuzzInstruction::Typed(generated.instruction);
+
+        let start = self.start_call(limit);
+        let (outcome, timing) = self.timed(|| Outcome::of(&client.try_run(&fuzz_instruction)));
+        let charged = self.end_call(&start, limit);
+        if outcome == Outcome::Panic && !self.keep_panics {
+            return None;
+        }
+
+        let mut measurement = self.measurement(
+            seed,
+            syscall_name,
+            format!("{:?}", fuzz_instruction),
+            generated.description,
+            charged,
+            outcome,
+            timing,
+        );
+        measurement.size = generated.size;
+        Some(measurement)
+    }
+
+    fn measure_custom(
+        &self,
+        custom: &CustomContract,
+        seed: u64,
+        exclude_conversion: bool,
+        limit: Option<BudgetLimit>,
+    ) -> Option<Measurement> {
+        let env = &self.env;
+
+        let mut raw_data = [0u8; 512];
+        StdRng::seed_from_u64(seed).fill(&mut raw_data[..]);
+
+        let mut unstructured = Unstructured::new(&raw_data);
+
+        let before = BudgetSnapshot::take(env);
+        let call = custom.arbitrary_call(env, &mut unstructured).ok()?;
+        let conversion = self.conversion(&before, exclude_conversion);
+
+        let start = self.start_call(limit);
+        let (outcome, timing) = self.timed(|| {
+            Outcome::of(&env.try_invoke_contract::<Val, soroban_sdk::Error>(
+                &custom.contract_id,
+                &call.function,
+                call.args.clone(),
+            ))
+        });
+        let charged = self.end_call(&start, limit).plus(&conversion);
+        if outcome == Outcome::Panic && !self.keep_panics {
+            return None;
+        }
+
+        Some(self.measurement(
+            seed,
+            call.name,
+            format!("{:?}", call.args),
+            call.description,
+            charged,
+            outcome,
+            timing,
+        ))
+    }
+
+    // Host functions run inside a frame of the fuzz contract, as they would
+    // when called from it.
+    fn measure_host(
+        &self,
+        seed: u64,
+        exclude_conversion: bool,
+        limit: Option<BudgetLimit>,
+    ) -> Option<Measurement> {
+        let env = &self.env;
+
+        let mut raw_data = [0u8; 512];
+        StdRng::seed_from_u64(seed).fill(&mut raw_data[..]);
+
+        let mut unstructured = Unstructured::new(&raw_data);
+
+        let function = unstructured.choose(HOST_FUNCTIONS).ok()?;
+        let before = BudgetSnapshot::take(env);
+        let call = (function.arbitrary)(env, &mut unstructured).ok()?;
+        let conversion = self.conversion(&before, exclude_conversion);
+        let args = call.args.clone();
+
+        let start = self.start_call(limit);
+        let (outcome, timing) =
+            env.as_contract(&self.contract_id, || self.timed(|| call.invoke(env.host())));
+        let charged = self.end_call(&start, limit).plus(&conversion);
+        if outcome == Outcome::Panic && !self.keep_panics {
+            return None;
+        }
+
+        Some(self.measurement(
+            seed,
+            function.name,
+            args,
+            String::new(),
+            charged,
+            outcome,
+            timing,
+        ))
+    }
+
+    // Applies `limit`, if any, right before the call, so converting, biasing
+    // and preparing the input always run unlimited. Returns the snapshot the
+    // call's charges count from.
+    fn start_call(&self, limit: Option<BudgetLimit>) -> BudgetSnapshot {
+        if let Some(limit) = limit {
+            self.env.budget().reset_limits(limit.cpu, limit.mem);
+        }
+        BudgetSnapshot::take(&self.env)
+    }
+
+    // What the call charged since `start`. A limit is lifted again, so
+    // whatever follows the call runs unlimited.
+    fn end_call(&self, start: &BudgetSnapshot, limit: Option<BudgetLimit>) -> BudgetSnapshot {
+        let charged = BudgetSnapshot::take(&self.env).since(start);
+        if limit.is_some() {
+            self.env.budget().reset_unlimited();
+        }
+        charged
+    }
+
+    // What converting the input charged since `before`, or nothing when it's
+    // excluded.
+    fn conversion(&self, before: &BudgetSnapshot, exclude_conversion: bool) -> BudgetSnapshot {
+        if exclude_conversion {
+            BudgetSnapshot::default()
+        } else {
+            BudgetSnapshot::take(&self.env).since(before)
+        }
+    }
+
+    // Runs `call` with wall time, context switches and hardware counters
+    // recorded around it.
+    fn timed(&self, call: impl FnOnce() -> Outcome) -> (Outcome, Timing) {
+        let alloc_before = self.track_alloc.then(AllocSnapshot::take);
+        let switches_before = noise::context_switches();
+        if let Some(counters) = &self.counters {
+            counters.start();
+        }
+        let before = Instant::now();
+
+        let outcome = fuzz_catch_panic(call).unwrap_or(Outcome::Panic);
+
+        let after = Instant::now();
+        let counters = self.counters.as_ref().map(|counters| counters.stop());
+        let context_switches = noise::context_switches().since(&switches_before);
+        let allocations = alloc_before.map(|before| before.since());
+
+        let timing = Timing {
+            duration: after.duration_since(before),
+            context_switches,
+            counters,
+            allocations,
+        };
+        (outcome, timing)
+    }
+
+    fn measurement(
+        &self,
+        seed: u64,
+        syscall_name: impl Into<Cow<'static, str>>,
+        syscall_input: String,
+        arbitrary_input: String,
+        charged: BudgetSnapshot,
+        outcome: Outcome,
+        timing: Timing,
+    ) -> Measurement {
+        Measurement {
+            seed,
+            worker: self.index,
+            syscall_name: syscall_name.into(),
+            syscall_input,
+            arbitrary_input,
+            cpu_instruction_cost: charged.cpu_instruction_cost,
+            memory_bytes_cost: charged.memory_bytes_cost,
+            duration: timing.duration,
+            contended: self.contended,
+            context_switches: timing.context_switches,
+            counters: timing.counters,
+            dispatch: None,
+            program_step: None,
+            outcome,
+            limit: None,
+            problem: None,
+            allocations: timing.allocations,
+            size: None,
+        }
+    }
+}
+
+struct Timing {
+    duration: Duration,
+    context_switches: ContextSwitches,
+    counters: Option<CounterValues>,
+    allocations: Option<AllocStats>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct BudgetSnapshot {
+    cpu_instruction_cost: u64,
+    memory_bytes_cost: u64,
+}
+
+impl BudgetSnapshot {
+    fn take(env: &Env) -> BudgetSnapshot {
+        BudgetSnapshot {
+            cpu_instruction_cost: env.budget().cpu_instruction_cost(),
+            memory_bytes_cost: env.budget().memory_bytes_cost(),
+        }
+    }
+
+    fn since(&self, earlier: &BudgetSnapshot) -> BudgetSnapshot {
+        BudgetSnapshot {
+            cpu_instruction_cost: self.cpu_instruction_cost - earlier.cpu_instruction_cost,
+            memory_bytes_cost: self.memory_bytes_cost - earlier.memory_bytes_cost,
+        }
+    }
+
+    fn plus(&self, other: &BudgetSnapshot) -> BudgetSnapshot {
+        BudgetSnapshot {
+            cpu_instruction_cost: self.cpu_instruction_cost + other.cpu_instruction_cost,
+            memory_bytes_cost: self.memory_bytes_cost + other.memory_bytes_cost,
+        }
+    }
+}
+
+// Worker `index` takes every `workers`-th iteration, so the seed space
+// `config.seed..config.seed + config.iterations` is covered exactly once.
+// Returns what couldn't be set up as asked.
+pub fn run(config: &Config, index: u64, tx: Sender<Measurement>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Some(core) = config.core_for_worker(index) {
+        if !noise::pin_current_thread(core) {
+            warnings.push(format!("failed to pin worker {} to cpu{}", index, core));
+        }
+    }
+    if config.raise_priority && !noise::raise_priority() {
+        warnings.push(format!("failed to raise priority of worker {}", index));
+    }
+
+    let worker = Worker::new(config, index);
+    if config.perf_counters && worker.counters.is_none() {
+        warnings.push(format!(
+            "perf_event_open failed; worker {} has no hardware counters",
+            index
+        ));
+    }
+
+    let seeds: Vec<u64> = (index..config.iterations)
+        .step_by(config.workers as usize)
+        .map(|iteration| config.seed.wrapping_add(iteration))
+        .collect();
+
+    if let Some(limits) = &config.limits {
+        for seed in seeds {
+            for measurement in worker.measure_limits(seed, limits) {
+                tx.send(measurement).unwrap();
+            }
+        }
+        return warnings;
+    }
+
+    if let Some(sweep) = &config.sweep {
+        for seed in seeds {
+            for measurement in worker.measure_sweep(seed, sweep) {
+                tx.send(measurement).unwrap();
+            }
+        }
+        return warnings;
+    }
+
+    if let Some(program_len) = config.program_len {
+        for seed in seeds {
+            for measurement in worker.measure_program(seed, program_len) {
+                tx.send(measurement).unwrap();
+            }
+        }
+        return warnings;
+    }
+
+    for seed in seeds {
+        if let Some(measurement) = worker.measure_seed(seed) {
+            tx.send(measurement).unwrap();
+        }
+    }
+    warnings
+}
+
+// Starts `config.workers` workers; the receiver yields their measurements
+// until all of them finish.
+pub fn spawn_all(config: &Config) -> (Receiver<Measurement>, Vec<JoinHandle<Vec<String>>>) {
+    let (tx, rx) = mpsc::channel();
+
+    let handles = (0..config.workers)
+        .map(|index| {
+            let config = config.clone();
+            let tx = tx.clone();
+            thread::Builder::new()
+                .name(format!("worker-{}", index))
+                // Match the main thread's stack; the host recurses deeply on nested values.
+                .stack_size(8 * 1024 * 1024)
+                .spawn(move || run(&config, index, tx))
+                .unwrap()
+        })
+        .collect();
+
+    (rx, handles)
+}
@@ -0,0 +1,134 @@
+// Serialized `ScVal` trees and deep vector nests for `DeserializeFromBytes`.
+
+use crate::fuzzcontract::{TypedFuzzInstruction, TypedModBuf};
+use crate::generator::Generated;
+use rand::rngs::StdRng;
+use rand::Rng;
+use soroban_sdk::xdr::{
+    Int128Parts, ScBytes, ScMap, ScMapEntry, ScSymbol, ScVal, ScValType, ScVec, UInt128Parts,
+    WriteXdr,
+};
+use soroban_sdk::{Bytes, Env};
+
+#[derive(Clone, Debug)]
+pub struct XdrConfig {
+    // Maximum nesting of the generated trees; a leaf is depth 0.
+    pub depth: u32,
+    // Maximum number of elements per vector or map.
+    pub breadth: u32,
+    // Maximum nesting of the deep cases, which are single-element vectors
+    // inside each other.
+    pub deep_max: u32,
+}
+
+impl Default for XdrConfig {
+    fn default() -> XdrConfig {
+        XdrConfig {
+            depth: 4,
+            breadth: 8,
+            deep_max: 1000,
+        }
+    }
+}
+
+// Share of inputs that are deep cases.
+const DEEP_SHARE: f64 = 0.125;
+
+// The node count goes in the `Size` column.
+pub fn generate(env: &Env, rng: &mut StdRng, config: &XdrConfig) -> Generated {
+    let (xdr, description, nodes) = if rng.gen_bool(DEEP_SHARE) {
+        let depth = rng.gen_range(1..=config.deep_max);
+        (
+            deep(depth),
+            format!("deep depth={}", depth),
+            depth as u64 + 1,
+        )
+    } else {
+        let mut nodes = 0;
+        let depth = rng.gen_range(0..=config.depth);
+        let val = tree(rng, config, depth, &mut nodes);
+        (
+            val.to_xdr().unwrap(),
+            format!("tree depth<={} nodes={}", depth, nodes),
+            nodes,
+        )
+    };
+
+    Generated {
+        instruction: TypedFuzzInstruction::Buf(TypedModBuf::DeserializeFromBytes(
+            Bytes::from_slice(env, &xdr),
+        )),
+        description: format!("{} len={}", description, xdr.len()),
+        size: Some(nodes),
+    }
+}
+
+fn tree(rng: &mut StdRng, config: &XdrConfig, depth: u32, nodes: &mut u64) -> ScVal {
+    *nodes += 1;
+    if depth == 0 || rng.gen_bool(0.25) {
+        return leaf(rng);
+    }
+
+    let len = rng.gen_range(0..=config.breadth);
+    if rng.gen() {
+        let elements: Vec<ScVal> = (0..len)
+            .map(|_| tree(rng, config, depth - 1, nodes))
+            .collect();
+        ScVal::Vec(Some(ScVec(elements.try_into().unwrap())))
+    } else {
+        // The host rejects maps whose keys aren't sorted and unique.
+        let entries: Vec<ScMapEntry> = (0..len)
+            .map(|key| ScMapEntry {
+                key: ScVal::U32(key),
+                val: tree(rng, config, depth - 1, nodes),
+            })
+            .collect();
+        *nodes += len as u64;
+        ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+    }
+}
+
+fn leaf(rng: &mut StdRng) -> ScVal {
+    match rng.gen_range(0..10) {
+        0 => ScVal::Void,
+        1 => ScVal::Bool(rng.gen()),
+        2 => ScVal::U32(rng.gen()),
+        3 => ScVal::I32(rng.gen()),
+        4 => ScVal::U64(rng.gen()),
+        5 => ScVal::I64(rng.gen()),
+        6 => ScVal::U128(UInt128Parts {
+            hi: rng.gen(),
+            lo: rng.gen(),
+        }),
+        7 => ScVal::I128(Int128Parts {
+            hi: rng.gen(),
+            lo: rng.gen(),
+        }),
+        8 => {
+            let mut bytes = vec![0u8; rng.gen_range(0..=64)];
+            rng.fill(&mut bytes[..]);
+            ScVal::Bytes(ScBytes(bytes.try_into().unwrap()))
+        }
+        _ => {
+            const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_";
+            let symbol: Vec<u8> = (0..rng.gen_range(0..=32))
+                .map(|_| CHARS[rng.gen_range(0..CHARS.len())])
+                .collect();
+            ScVal::Symbol(ScSymbol(symbol.try_into().unwrap()))
+        }
+    }
+}
+
+// Encoded by hand, so the nesting isn't bounded by the XDR writer's
+// recursion.
+fn deep(depth: u32) -> Vec<u8> {
+    let mut xdr = Vec::new();
+    for _ in 0..depth {
+        xdr.extend((ScValType::Vec as i32).to_be_bytes());
+        // Present, one element.
+        xdr.extend(1u32.to_be_bytes());
+        xdr.extend(1u32.to_be_bytes());
+    }
+    xdr.extend((ScValType::Void as i32).to_be_bytes());
+    xdr
+}